@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufReader, ErrorKind, Read, Write},
+    net::Shutdown,
+    os::unix::net::UnixStream,
+    path::Path,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+
+use crate::structs::*;
+
+use super::ClientEvent;
+
+/// requests waiting for a response, by request id
+type Pending = Arc<Mutex<HashMap<u32, oneshot::Sender<Response>>>>;
+
+/// write half of a connection, shared by the client and its events
+pub(super) struct Writer {
+    /// where requests are written to
+    stream: Mutex<Box<dyn Write + Send>>,
+
+    /// encoding requests are written in
+    encoding: Mutex<Encoding>,
+
+    /// last id given to a request
+    request_id: AtomicU32,
+}
+
+impl Writer {
+    /// write a request, returns its id
+    pub(super) fn send(&self, target: Discriminator, content: RequestContent) -> io::Result<u32> {
+        let id = self.request_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let request = Request::new_with_id(target, content, id);
+        let frame = self
+            .encoding
+            .lock()
+            .unwrap()
+            .encode(&request)
+            .map_err(io::Error::other)?;
+
+        let mut stream = self.stream.lock().unwrap();
+        stream.write_all(&frame)?;
+        stream.flush()?;
+        Ok(id)
+    }
+}
+
+/// a connection to ccanvas from inside a component
+pub struct Client {
+    /// write half of the connection
+    writer: Arc<Writer>,
+
+    /// requests waiting for a response
+    pending: Pending,
+
+    /// events recieved, in order
+    events: tokio::sync::Mutex<UnboundedReceiver<ClientEvent>>,
+
+    /// socket to close when the client is dropped
+    socket: Option<UnixStream>,
+
+    /// features supported by both sides
+    features: Vec<Feature>,
+
+    /// limits of what requests can contain
+    limits: Limits,
+}
+
+impl Client {
+    /// connect to ccanvas through connection.sock in the storage folder,
+    /// which is the working directory of spawned components
+    pub async fn connect() -> io::Result<Self> {
+        Self::connect_to("connection.sock", Encoding::Json).await
+    }
+
+    /// connect to ccanvas through a socket
+    /// and switch to an encoding after the handshake
+    pub async fn connect_to(path: impl AsRef<Path>, encoding: Encoding) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let stream = tokio::task::spawn_blocking(move || {
+            // the socket may not be there yet if the component just started
+            let mut retries = 50;
+            loop {
+                match UnixStream::connect(&path) {
+                    Err(e)
+                        if retries > 0
+                            && matches!(
+                                e.kind(),
+                                ErrorKind::NotFound | ErrorKind::ConnectionRefused
+                            ) =>
+                    {
+                        retries -= 1;
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                    stream => return stream,
+                }
+            }
+        })
+        .await
+        .map_err(io::Error::other)??;
+
+        let mut client = Self::start(stream.try_clone()?, stream.try_clone()?);
+        client.socket = Some(stream);
+        client.hello(encoding).await?;
+        Ok(client)
+    }
+
+    /// talk to ccanvas through stdin and stdout,
+    /// for components spawned with the stdio transport
+    pub async fn stdio(encoding: Encoding) -> io::Result<Self> {
+        let mut client = Self::start(io::stdin(), io::stdout());
+        client.hello(encoding).await?;
+        Ok(client)
+    }
+
+    /// start reading responses, json is spoken until the handshake is done
+    fn start(reader: impl Read + Send + 'static, writer: impl Write + Send + 'static) -> Self {
+        let writer = Arc::new(Writer {
+            stream: Mutex::new(Box::new(writer)),
+            encoding: Mutex::new(Encoding::Json),
+            request_id: AtomicU32::new(0),
+        });
+        let pending = Pending::default();
+        let (events_send, events_recv) = mpsc::unbounded_channel();
+
+        Self::read_responses(reader, writer.clone(), pending.clone(), events_send);
+
+        Self {
+            writer,
+            pending,
+            events: tokio::sync::Mutex::new(events_recv),
+            socket: None,
+            features: Vec::new(),
+            limits: Limits::default(),
+        }
+    }
+
+    /// say hello to ccanvas, fails if the version is not accepted
+    async fn hello(&mut self, encoding: Encoding) -> io::Result<()> {
+        let response = self
+            .request(
+                Discriminator::default(),
+                RequestContent::Hello {
+                    version: PROTOCOL_VERSION,
+                    features: Feature::SUPPORTED.to_vec(),
+                    encoding,
+                },
+            )
+            .await?;
+
+        match response.content() {
+            ResponseContent::Success {
+                content:
+                    ResponseSuccess::Hello {
+                        features,
+                        limits,
+                        encoding,
+                        ..
+                    },
+            } => {
+                // ccanvas reads in the new encoding after the hello
+                *self.writer.encoding.lock().unwrap() = *encoding;
+                self.features = features.clone();
+                self.limits = *limits;
+                Ok(())
+            }
+            ResponseContent::Error { message, .. } => Err(io::Error::other(message.clone())),
+            content => Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("unexpected response to hello: {content:?}"),
+            )),
+        }
+    }
+
+    /// read responses until the connection closes
+    /// the encoding is switched after the response to the hello
+    fn read_responses(
+        reader: impl Read + Send + 'static,
+        writer: Arc<Writer>,
+        pending: Pending,
+        events: UnboundedSender<ClientEvent>,
+    ) {
+        tokio::task::spawn_blocking(move || {
+            let mut reader = BufReader::new(reader);
+            let mut encoding = Encoding::Json;
+            let max_size = Limits::default().max_frame_size;
+
+            while let Ok(Some(frame)) = encoding.read_frame(&mut reader, max_size) {
+                let response: Response = match encoding.decode(&frame) {
+                    Ok(response) => response,
+                    Err(_) => continue,
+                };
+
+                match (response.content(), response.request()) {
+                    (ResponseContent::Event { content }, _) => {
+                        let _ = events.send(ClientEvent::new(
+                            content.clone(),
+                            response.id(),
+                            writer.clone(),
+                        ));
+                    }
+                    (content, Some(request)) => {
+                        if let ResponseContent::Success {
+                            content: ResponseSuccess::Hello { encoding: new, .. },
+                        } = content
+                        {
+                            encoding = *new;
+                        }
+
+                        if let Some(sender) = pending.lock().unwrap().remove(&request) {
+                            let _ = sender.send(response);
+                        }
+                    }
+                    // errors that cannot be traced back to a request
+                    _ => {}
+                }
+            }
+
+            // requests that will never be responded to
+            pending.lock().unwrap().clear();
+        });
+    }
+
+    /// features supported by both ccanvas and the client
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// limits of what requests can contain
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// send a request and wait for its response
+    /// an empty target is filled in by ccanvas
+    pub async fn request(
+        &self,
+        target: Discriminator,
+        content: RequestContent,
+    ) -> io::Result<Response> {
+        let (sender, reciever) = oneshot::channel();
+        // hold the lock so the response cannot arrive before the sender is in the map
+        let id = {
+            let mut pending = self.pending.lock().unwrap();
+            let id = self.writer.send(target, content)?;
+            pending.insert(id, sender);
+            id
+        };
+
+        reciever.await.map_err(|_| {
+            io::Error::new(
+                ErrorKind::ConnectionAborted,
+                format!("connection closed before request {id} is responded to"),
+            )
+        })
+    }
+
+    /// add subscription to a channel with priority
+    pub async fn subscribe(
+        &self,
+        channel: Subscription,
+        priority: Option<u32>,
+    ) -> io::Result<Response> {
+        self.request(
+            Discriminator::default(),
+            RequestContent::Subscribe {
+                channel,
+                priority,
+                component: None,
+            },
+        )
+        .await
+    }
+
+    /// render something to the region of the component
+    pub async fn render(&self, content: RenderRequest, flush: bool) -> io::Result<Response> {
+        self.request(
+            Discriminator::default(),
+            RequestContent::Render {
+                content,
+                flush,
+                component: None,
+            },
+        )
+        .await
+    }
+
+    /// wait for the next event, None if the connection is closed
+    pub async fn next_event(&self) -> Option<ClientEvent> {
+        self.events.lock().await.recv().await
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        if let Some(socket) = &self.socket {
+            let _ = socket.shutdown(Shutdown::Both);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use crate::structs::{Discriminator, EventSerde, RequestContent};
+
+use super::Writer;
+
+/// an event recieved from ccanvas
+///
+/// ccanvas waits for the event to be confirmed before passing it on,
+/// this is done when the event is dropped
+pub struct ClientEvent {
+    /// the event itself
+    content: EventSerde,
+
+    /// id of the response the event came in
+    id: u32,
+
+    /// false to stop the event from reaching other components
+    pass: bool,
+
+    /// where the confirmation is sent to
+    writer: Arc<Writer>,
+}
+
+impl ClientEvent {
+    pub(super) fn new(content: EventSerde, id: u32, writer: Arc<Writer>) -> Self {
+        Self {
+            content,
+            id,
+            pass: true,
+            writer,
+        }
+    }
+
+    /// the event itself
+    pub fn content(&self) -> &EventSerde {
+        &self.content
+    }
+
+    /// stop the event from being passed to other components
+    pub fn capture(mut self) {
+        self.pass = false;
+    }
+}
+
+impl Drop for ClientEvent {
+    fn drop(&mut self) {
+        let _ = self.writer.send(
+            Discriminator::default(),
+            RequestContent::ConfirmRecieve {
+                id: self.id,
+                pass: self.pass,
+            },
+        );
+    }
+}
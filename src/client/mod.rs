@@ -0,0 +1,5 @@
+mod connection;
+pub use connection::*;
+
+mod event;
+pub use event::*;
@@ -1,6 +1,7 @@
+#[cfg(feature = "client")]
+pub mod client;
 pub mod structs;
 pub mod term;
 pub mod traits;
-pub mod values;
 
 pub use structs::Error;
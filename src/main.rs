@@ -1,32 +1,41 @@
-use std::{env, sync::Arc, time::Duration};
+use std::{env, time::Duration};
 
-use ccanvas::{
-    structs::Space,
-    term::{enter, exit},
-};
+use ccanvas::structs::{schema, Canvas, Transport};
 use tokio::runtime::Runtime;
 
 fn main() {
     let args: Vec<String> = env::args().skip(1).collect();
+
+    // print the protocol schema instead of starting the canvas
+    if args.first().map(String::as_str) == Some("--schema") {
+        println!("{}", serde_json::to_string_pretty(&schema()).unwrap());
+        return;
+    }
+
     if args.len() < 2 {
-        println!("Bad arguments: expect `ccanvas [label] [command] (args..)`");
+        println!(
+            "Bad arguments: expect `ccanvas [label] [command] (args..)` or `ccanvas --schema`"
+        );
         return;
     }
 
     let runtime = Runtime::new().unwrap();
 
-    runtime.block_on(enter());
-
-    // creates new master space
-    let master = Arc::new(runtime.block_on(Space::new("master".to_string())));
-    let handle = runtime.spawn(Space::listen(master.clone()));
-    runtime
-        .block_on(master.spawn(args[0].clone(), args[1].clone(), args[2..].to_vec()))
+    // creates the canvas, with a master space running the process
+    let handle = runtime
+        .block_on(
+            Canvas::builder()
+                .spawn(
+                    args[0].clone(),
+                    args[1].clone(),
+                    args[2..].to_vec(),
+                    Transport::default(),
+                )
+                .build(),
+        )
         .unwrap();
-    runtime.block_on(handle).unwrap();
 
     // get rid of everyting, kills all processes, etc
+    runtime.block_on(handle.wait());
     runtime.shutdown_timeout(Duration::from_secs(0));
-
-    exit();
 }
@@ -1,23 +1,15 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::sync::OnceCell;
-
-static mut DISCRIM: OnceCell<u32> = OnceCell::const_new_with(0);
-/// get a unique discriminator chunk
-pub fn discrim() -> u32 {
-    let discrim = unsafe { DISCRIM.get_mut().unwrap() };
-    *discrim += 1;
-    *discrim
-}
 
 /// a unique path id for every component
-#[derive(Default, PartialEq, Eq, Clone, Debug, Serialize, Deserialize, Hash)]
+#[derive(Default, PartialEq, Eq, Clone, Debug, Serialize, Deserialize, Hash, JsonSchema)]
 pub struct Discriminator(pub Vec<u32>);
 
 impl Discriminator {
-    /// create new child component
-    pub fn new_child(&self) -> Self {
+    /// create new child component with a unique chunk
+    pub fn new_child(&self, chunk: u32) -> Self {
         let mut new_discrim = self.0.to_vec();
-        new_discrim.push(discrim());
+        new_discrim.push(chunk);
         Self(new_discrim)
     }
 
@@ -4,6 +4,9 @@ pub use discriminator::*;
 mod focus;
 pub use focus::*;
 
+mod native;
+pub use native::*;
+
 mod passes;
 pub use passes::*;
 
@@ -18,3 +21,6 @@ pub use subscription::*;
 
 mod subscriptions;
 pub use subscriptions::*;
+
+mod transport;
+pub use transport::*;
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+
+use crate::traits::{Component, NativeComponent};
+
+use crate::structs::*;
+
+/// a native rust component running in a space
+pub struct Native {
+    /// the canvas the component is in
+    canvas: Arc<Canvas>,
+
+    /// name of the component
+    label: String,
+
+    /// unique identifier of the component
+    discrim: Discriminator,
+
+    /// data storage for self
+    pool: Pool,
+
+    /// shared storage folder for self
+    storage: Storage,
+
+    /// the component itself
+    component: Arc<dyn NativeComponent>,
+
+    /// handle given to the component
+    handle: NativeHandle,
+
+    /// task running the start of the component
+    starter: JoinHandle<()>,
+}
+
+impl Native {
+    /// add a native component under a space and start it
+    pub async fn new(
+        canvas: Arc<Canvas>,
+        label: String,
+        parent: &Discriminator,
+        component: Arc<dyn NativeComponent>,
+    ) -> Self {
+        let discrim = parent.new_child(canvas.discrim());
+        let storage = Storage::new(canvas.root(), &discrim).await;
+        let handle = NativeHandle {
+            canvas: canvas.clone(),
+            discrim: discrim.clone(),
+        };
+
+        let starter = {
+            let component = component.clone();
+            let handle = handle.clone();
+            tokio::spawn(async move { component.start(handle).await })
+        };
+
+        Self {
+            canvas,
+            label,
+            discrim,
+            pool: Pool::default(),
+            storage,
+            component,
+            handle,
+            starter,
+        }
+    }
+
+    /// handle a request targeted at self
+    pub async fn handle(&self, packet: &mut Packet<Request, Response>) {
+        let content = match packet.get().content() {
+            // messages are passed to the component as events
+            RequestContent::Message {
+                content,
+                sender,
+                target,
+            } => {
+                let event = Event::Message {
+                    sender: sender.clone(),
+                    target: target.clone(),
+                    content: content.clone(),
+                };
+                let component = self.component.clone();
+                let handle = self.handle.clone();
+                tokio::spawn(async move { component.event(&event, &handle).await });
+
+                ResponseContent::Success {
+                    content: ResponseSuccess::MessageDelivered,
+                }
+            }
+            // spawn should be passed to spaces
+            RequestContent::Spawn { .. } => ResponseContent::error(ResponseError::InvalidTarget),
+            _ => ResponseContent::error(ResponseError::InvalidRequest),
+        };
+
+        let _ = packet.respond_with(&self.canvas, content);
+    }
+}
+
+#[async_trait]
+impl Component for Native {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn discrim(&self) -> &Discriminator {
+        &self.discrim
+    }
+
+    fn pool(&self) -> &Pool {
+        &self.pool
+    }
+
+    fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    async fn pass(&self, event: &mut Event) -> Unevaluated<bool> {
+        if let Event::RequestPacket(packet) = event {
+            self.handle(packet).await;
+            return false.into();
+        }
+
+        let event = event.clone();
+        let component = self.component.clone();
+        let handle = self.handle.clone();
+        // dont block, same as processes
+        Unevaluated::Unevaluated(tokio::spawn(async move {
+            component.event(&event, &handle).await
+        }))
+    }
+
+    async fn exit(&self) {
+        self.starter.abort();
+    }
+}
+
+impl Drop for Native {
+    fn drop(&mut self) {
+        self.starter.abort();
+    }
+}
+
+/// what a native component uses to talk to the canvas
+#[derive(Clone)]
+pub struct NativeHandle {
+    /// the canvas the component is in
+    canvas: Arc<Canvas>,
+
+    /// discriminator of the component
+    discrim: Discriminator,
+}
+
+impl NativeHandle {
+    /// discriminator of the component
+    pub fn discrim(&self) -> &Discriminator {
+        &self.discrim
+    }
+
+    /// send a request and wait for its response
+    /// the target is filled in the same way as for requests from processes
+    pub async fn request(&self, target: Discriminator, content: RequestContent) -> Response {
+        let mut request = Request::new(&self.canvas, target, content);
+
+        let error = match request.content().feature() {
+            Some(feature) if !self.canvas.supports(feature) => {
+                Some(ResponseError::FeatureDisabled { feature })
+            }
+            _ => request.address(&self.discrim).err(),
+        };
+
+        match error {
+            Some(error) => Response::new_with_request(
+                &self.canvas,
+                ResponseContent::error(error),
+                *request.id(),
+            ),
+            None => request.send(&self.canvas).await,
+        }
+    }
+
+    /// draw to the region of the component
+    pub async fn render(&self, content: RenderRequest, flush: bool) -> Response {
+        self.request(
+            Discriminator::default(),
+            RequestContent::Render {
+                content,
+                flush,
+                component: None,
+            },
+        )
+        .await
+    }
+
+    /// subscribe to a channel, events are passed to `NativeComponent::event()`
+    pub async fn subscribe(&self, channel: Subscription, priority: Option<u32>) -> Response {
+        self.request(
+            Discriminator::default(),
+            RequestContent::Subscribe {
+                channel,
+                priority,
+                component: None,
+            },
+        )
+        .await
+    }
+}
@@ -112,6 +112,19 @@ impl Passes {
         })
     }
 
+    /// list all subscriptions of a component with their priorities
+    pub fn subscriptions_of(&self, discrim: &Discriminator) -> Vec<(Subscription, Option<u32>)> {
+        self.subscriptions
+            .iter()
+            .filter_map(|(subscription, items)| {
+                items
+                    .iter()
+                    .find(|item| item.discrim() == discrim)
+                    .map(|item| (subscription.clone(), item.priority))
+            })
+            .collect()
+    }
+
     /// list subscribers of all the subscriptions specified
     /// sorted + no duplicates
     pub fn subscribers(&self, subscription: &[Subscription]) -> Vec<PassItem> {
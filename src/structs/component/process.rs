@@ -1,16 +1,17 @@
 use std::{
     collections::{hash_map::Entry, HashMap},
-    io::{Read, Write},
-    os::unix::net::{UnixListener, UnixStream},
-    process::Stdio,
+    os::unix::process::CommandExt,
+    process::{self, Stdio},
     sync::Arc,
 };
 
 use async_trait::async_trait;
 use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
     process::{Child, Command},
     sync::{
-        mpsc::{self, UnboundedSender},
+        mpsc::{self, Sender, UnboundedSender},
         oneshot, Mutex,
     },
     task::JoinHandle,
@@ -22,6 +23,9 @@ use crate::structs::*;
 
 /// single runnable process
 pub struct Process {
+    /// the canvas the process is in
+    canvas: Arc<Canvas>,
+
     /// name of the current process
     label: String,
 
@@ -43,6 +47,12 @@ pub struct Process {
     /// handle to the task responsible for listening to requests
     listener: JoinHandle<()>,
 
+    /// handle to the task responsible for accepting persistent connections
+    connector: JoinHandle<()>,
+
+    /// handle to the task responsible for handling requests
+    handler: JoinHandle<()>,
+
     /// handle to the task responsible for responding
     responder: JoinHandle<()>,
 
@@ -60,15 +70,27 @@ impl PartialEq for Process {
 }
 
 impl Process {
+    /// responses waiting to be written to a persistent connection
+    const QUEUED_RESPONSES: usize = 64;
+
     /// spawns a new process with command
     pub async fn spawn(
+        canvas: Arc<Canvas>,
         label: String,
         parent: &Discriminator,
         command: String,
         args: Vec<String>,
+        transport: Transport,
     ) -> Result<Self, std::io::Error> {
-        let discrim = parent.new_child();
-        let storage = Storage::new(&discrim).await;
+        if transport == Transport::Stdio && !canvas.supports(Feature::Stdio) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "stdio transport is disabled",
+            ));
+        }
+
+        let discrim = parent.new_child(canvas.discrim());
+        let storage = Storage::new(canvas.root(), &discrim).await;
 
         // a new sender is pushed to the map whenever something is sent to the component
         // the sender returns a boolean, if true then the event will not be captured, vice versa
@@ -80,35 +102,73 @@ impl Process {
         // the component should send requests to this path
         let socket_path = storage.path().join("requests.sock");
         Storage::remove_if_exist(&socket_path).await.unwrap();
-        let child = Arc::new(Mutex::new(
-            Command::new(&command)
-                .kill_on_drop(true)
-                .args(&args)
-                .current_dir(storage.path())
-                .stdin(Stdio::null())
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()?,
-        ));
+        // or connect to this path and keep the connection open
+        let connection_path = storage.path().join("connection.sock");
+        Storage::remove_if_exist(&connection_path).await.unwrap();
+        // stdio is only piped if it is used for requests
+        let stdio = || match transport {
+            Transport::Socket => Stdio::null(),
+            Transport::Stdio => Stdio::piped(),
+        };
+        // in its own process group, so everything it starts can be killed along with it
+        let mut group = process::Command::new(&command);
+        group.process_group(0);
+        let mut child = Command::from(group)
+            .kill_on_drop(true)
+            .args(&args)
+            .current_dir(storage.path())
+            .stdin(stdio())
+            .stdout(stdio())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let pipes = match transport {
+            Transport::Socket => None,
+            Transport::Stdio => Some((child.stdin.take().unwrap(), child.stdout.take().unwrap())),
+        };
+        let child = Arc::new(Mutex::new(child));
 
         let (responder_send, mut responder_recv): (UnboundedSender<Response>, _) =
             mpsc::unbounded_channel();
 
         let (set_socket_send, set_socket_recv): (oneshot::Sender<()>, _) = oneshot::channel();
 
+        // queues of the writers of persistent connections
+        let (connection_send, mut connection_recv) = mpsc::unbounded_channel();
+
         // the responder task recieve Response
         // serialise it and send it to the component, if it specified a socket to send to
+        // or through the persistent connection, if there is one
         let responder = {
+            let canvas = canvas.clone();
             let confirm_handles = confirm_handles.clone();
             let child = child.clone();
             let discrim = discrim.clone();
             tokio::spawn(async move {
                 // by default there is no socket
                 let mut socket = None;
+                let mut connection: Option<Sender<(u32, Vec<u8>)>> = None;
+                // every new connection starts with json
+                let mut encoding = Encoding::Json;
                 let mut socket_confirm = Some(set_socket_send);
                 // let child = child;
 
-                while let Some(res) = responder_recv.recv().await {
+                loop {
+                    let res = tokio::select! {
+                        Some(writer) = connection_recv.recv() => {
+                            connection = Some(writer);
+                            encoding = Encoding::Json;
+                            // a connection is as good as a socket
+                            if socket_confirm.is_some() {
+                                let _ = std::mem::take(&mut socket_confirm).unwrap().send(());
+                            }
+                            continue;
+                        }
+                        res = responder_recv.recv() => match res {
+                            Some(res) => res,
+                            None => break,
+                        },
+                    };
+
                     let confirm_handles = confirm_handles.clone();
                     // this special "response" is recieved when a SetSocket request
                     // is sent by the component
@@ -120,35 +180,58 @@ impl Process {
                         continue;
                     }
 
+                    // responses must arrive in order, so they are queued right here
+                    // waiting for space if the component is not reading
+                    if let Some(writer) = &connection {
+                        #[cfg(feature = "log")]
+                        log::info!("{discrim:?} sent {res:?}");
+                        let msg = encoding.encode(&res).unwrap();
+                        if writer.send((res.id(), msg)).await.is_ok() {
+                            // the handshake response is the last one before switching
+                            if let ResponseContent::Success {
+                                content:
+                                    ResponseSuccess::Hello {
+                                        encoding: negotiated,
+                                        ..
+                                    },
+                            } = res.content()
+                            {
+                                encoding = *negotiated;
+                            }
+                            continue;
+                        }
+
+                        // the writer has stopped, fall back to the socket
+                        connection = None;
+                        if socket.is_none() {
+                            confirm_handles.lock().await.remove(&res.id());
+                            Self::drop_if_exited(&canvas, &child, &discrim).await;
+                            continue;
+                        }
+                    }
+
                     // only send a message when a socket is specified
                     if let Some(socket) = &socket {
                         #[cfg(feature = "log")]
                         log::info!("{discrim:?} sent {res:?}");
                         let socket = socket.clone();
+                        let canvas = canvas.clone();
                         let child = child.clone();
                         let discrim = discrim.clone();
                         tokio::spawn(async move {
                             // check if the child process has crashed
-                            if child.lock().await.try_wait().unwrap().is_some() {
-                                Event::send(Event::RequestPacket(
-                                    Packet::new(Request::new(
-                                        discrim.clone().immediate_parent().unwrap(),
-                                        RequestContent::Drop {
-                                            // if true, then tell the parent space to drop it
-                                            discrim: Some(discrim),
-                                        },
-                                    ))
-                                    .0,
-                                ))
-                            }
+                            Self::drop_if_exited(&canvas, &child, &discrim).await;
 
-                            if let Ok(mut stream) = UnixStream::connect(socket) {
-                                stream
+                            let sent = match UnixStream::connect(socket).await {
+                                Ok(mut stream) => stream
                                     .write_all(serde_json::to_vec(&res).unwrap().as_slice())
-                                    .unwrap();
-                                stream.flush().unwrap();
-                            } else {
-                                // if send failed, it is impossible to get a response message
+                                    .await
+                                    .is_ok(),
+                                Err(_) => false,
+                            };
+
+                            // if send failed, it is impossible to get a response message
+                            if !sent {
                                 confirm_handles.lock().await.remove(&res.id());
                             }
                         });
@@ -157,42 +240,217 @@ impl Process {
             })
         };
 
-        // the listener listens to event and handles it, that all
-        let listener = {
-            let discrim = discrim.clone();
+        // each persistent connection gets its own writer
+        let writer = {
+            let canvas = canvas.clone();
             let confirm_handles = confirm_handles.clone();
+            let child = child.clone();
+            let discrim = discrim.clone();
+            move |stream| {
+                Self::write_responses(
+                    canvas.clone(),
+                    stream,
+                    confirm_handles.clone(),
+                    child.clone(),
+                    discrim.clone(),
+                )
+            }
+        };
+
+        // requests from all connections are handled here
+        let (requests_send, mut requests_recv): (UnboundedSender<Request>, _) =
+            mpsc::unbounded_channel();
+
+        // the listener accepts one request per connection, that all
+        let listener = {
+            let canvas = canvas.clone();
+            let requests = requests_send.clone();
             let responder = responder_send.clone();
-            let storage = storage.clone();
             tokio::spawn(async move {
                 // creates a socket and listens to it
-                let socket =
-                    tokio::task::block_in_place(|| UnixListener::bind(socket_path).unwrap());
-                let mut incoming = socket.incoming();
+                let socket = UnixListener::bind(socket_path).unwrap();
+                let max_size = Limits::default().max_frame_size;
 
-                while let Some(stream) = tokio::task::block_in_place(|| incoming.next()) {
+                loop {
                     // give up if the stream is errorneous
-                    let mut stream = match stream {
-                        Ok(stream) => stream,
+                    let mut stream = match socket.accept().await {
+                        Ok((stream, _)) => stream,
                         Err(_) => continue,
                     };
 
-                    let mut msg = String::new();
-                    // another chance to give up
-                    if stream.read_to_string(&mut msg).is_err() {
-                        continue;
-                    }
+                    let msg = match Encoding::read_all(&mut stream, max_size).await {
+                        Ok(msg) => msg,
+                        Err(e) => {
+                            Self::reject(
+                                &canvas,
+                                &responder,
+                                ResponseError::ParseError {
+                                    reason: e.to_string(),
+                                },
+                                None,
+                            );
+                            continue;
+                        }
+                    };
 
-                    // a third chance to give up
-                    let mut request: Request = match serde_json::from_str(&msg) {
+                    let mut request = match Request::decode(&msg, Encoding::Json) {
                         Ok(req) => req,
+                        Err((error, id)) => {
+                            Self::reject(&canvas, &responder, error, id);
+                            continue;
+                        }
+                    };
+
+                    // binary encodings need framing, which one-shot sockets do not have
+                    if let RequestContent::Hello { encoding, .. } = request.content_mut() {
+                        *encoding = Encoding::Json;
+                    }
+
+                    let _ = requests.send(request);
+                }
+            })
+        };
+
+        // the connector accepts persistent connections
+        // with newline delimited requests and responses in both directions
+        let connector = {
+            let canvas = canvas.clone();
+            let requests = requests_send.clone();
+            let responder = responder_send.clone();
+            let connection_send = connection_send.clone();
+            let writer = writer.clone();
+            tokio::spawn(async move {
+                // there is no connection.sock to connect to
+                if !canvas.supports(Feature::Connection) {
+                    return;
+                }
+
+                let socket = UnixListener::bind(connection_path).unwrap();
+
+                loop {
+                    let (reader, stream) = match socket.accept().await {
+                        Ok((stream, _)) => stream.into_split(),
                         Err(_) => continue,
                     };
 
+                    // responses are written to the latest connection
+                    let _ = connection_send.send(writer(Box::new(stream)));
+
+                    Self::read_requests(
+                        canvas.clone(),
+                        reader,
+                        requests.clone(),
+                        responder.clone(),
+                    );
+                }
+            })
+        };
+
+        // stdout and stdin work just like a persistent connection
+        if let Some((stdin, stdout)) = pipes {
+            let _ = connection_send.send(writer(Box::new(stdin)));
+            Self::read_requests(
+                canvas.clone(),
+                stdout,
+                requests_send.clone(),
+                responder_send.clone(),
+            );
+        }
+
+        // the handler modifies requests and sends them on their way
+        let handler = {
+            let canvas = canvas.clone();
+            let discrim = discrim.clone();
+            let confirm_handles = confirm_handles.clone();
+            let responder = responder_send.clone();
+            let storage = storage.clone();
+            tokio::spawn(async move {
+                // protocol version of a failed handshake
+                let mut rejected = None;
+
+                while let Some(mut request) = requests_recv.recv().await {
                     #[cfg(feature = "log")]
                     log::info!("{discrim:?} recieved {request:?}");
 
-                    // modify requests
-                    match request.content_mut() {
+                    // a component that failed the handshake is not served
+                    // until it says hello with a compatible version
+                    if let Some(version) = rejected {
+                        if !matches!(
+                            request.content(),
+                            RequestContent::Hello { .. } | RequestContent::ConfirmRecieve { .. }
+                        ) {
+                            Self::reject(
+                                &canvas,
+                                &responder,
+                                ResponseError::IncompatibleVersion {
+                                    version,
+                                    min: MIN_PROTOCOL_VERSION,
+                                    max: PROTOCOL_VERSION,
+                                },
+                                Some(*request.id()),
+                            );
+                            continue;
+                        }
+                    }
+
+                    // ids above the limit are used by requests generated by ccanvas
+                    if *request.id() > Limits::default().max_request_id {
+                        Self::reject(
+                            &canvas,
+                            &responder,
+                            ResponseError::PermissionDenied,
+                            Some(*request.id()),
+                        );
+                        continue;
+                    }
+
+                    // features turned off for the canvas
+                    if let Some(feature) = request.content().feature() {
+                        if !canvas.supports(feature) {
+                            Self::reject(
+                                &canvas,
+                                &responder,
+                                ResponseError::FeatureDisabled { feature },
+                                Some(*request.id()),
+                            );
+                            continue;
+                        }
+                    }
+
+                    // requests answered by the process itself
+                    match request.content() {
+                        RequestContent::Hello {
+                            version, features, ..
+                        } => {
+                            // the handshake is answered by the process itself
+                            let content = if let Some(encoding) = Self::handshake_encoding(&request)
+                            {
+                                rejected = None;
+                                ResponseContent::Success {
+                                    content: ResponseSuccess::Hello {
+                                        version: PROTOCOL_VERSION,
+                                        ccanvas: env!("CARGO_PKG_VERSION").to_string(),
+                                        features: Feature::negotiate(canvas.features(), features),
+                                        cargo_features: cargo_features(),
+                                        limits: Limits::default(),
+                                        encoding,
+                                    },
+                                }
+                            } else {
+                                rejected = Some(*version);
+                                ResponseContent::error(ResponseError::IncompatibleVersion {
+                                    version: *version,
+                                    min: MIN_PROTOCOL_VERSION,
+                                    max: PROTOCOL_VERSION,
+                                })
+                            };
+                            let _ = responder.send(Response::new_with_request(
+                                &canvas,
+                                content,
+                                *request.id(),
+                            ));
+                            continue;
+                        }
                         RequestContent::ConfirmRecieve { id, pass } => {
                             // if the request is a confirmation to a response
                             // then confirm the response and unblock the self.pass() thing
@@ -209,50 +467,16 @@ impl Process {
                             });
                             continue;
                         }
-                        RequestContent::Subscribe {
-                            channel,
-                            priority,
-                            component: _,
-                        } => {
-                            // first add the channel to self as a record
-                            // and send a register event to the master space
-                            // which is eventually get sent to the parent space
-                            // and get added as into the passes
-                            *request.content_mut() = RequestContent::Subscribe {
-                                channel: channel.clone(),
-                                priority: *priority,
-                                component: Some(discrim.clone()),
-                            };
-                            *request.target_mut() = discrim.clone().immediate_parent().unwrap();
-                            let _ = responder.send(Response::new_with_request(
-                                ResponseContent::Success {
-                                    content: ResponseSuccess::SubscribeAdded,
-                                },
-                                *request.id(),
-                            ));
-                        }
-                        RequestContent::Unsubscribe {
-                            channel,
-                            component: _,
-                        } => {
-                            // first add the channel to self as a record
-                            // and send a register event to the master space
-                            // which is eventually get sent to the parent space
-                            // and get added as into the passes
-                            *request.content_mut() = RequestContent::Unsubscribe {
-                                channel: channel.clone(),
-                                component: Some(discrim.clone()),
-                            };
-                            *request.target_mut() = discrim.clone().immediate_parent().unwrap();
-                        }
                         RequestContent::SetSocket { path } => {
                             // these requests goes to self
                             let _ = responder.send(Response::new_with_request(
+                                &canvas,
                                 ResponseContent::SetSocket(storage.path().join(path)),
                                 *request.id(),
                             ));
 
                             let _ = responder.send(Response::new_with_request(
+                                &canvas,
                                 ResponseContent::Success {
                                     // this can never fail
                                     content: ResponseSuccess::ListenerSet,
@@ -261,34 +485,21 @@ impl Process {
                             ));
                             continue;
                         }
-                        RequestContent::Drop { discrim: to_drop } => {
-                            // this goes to parent space
-                            let to_drop = to_drop.as_ref().unwrap_or(&discrim).clone();
-                            *request.target_mut() = to_drop.clone().immediate_parent().unwrap();
-                            *request.content_mut() = RequestContent::Drop {
-                                discrim: Some(to_drop),
-                            };
-                        }
-                        RequestContent::Render { .. } => {
-                            // this goes to master space
-                            *request.target_mut() = Discriminator::master()
-                        }
-                        RequestContent::Spawn { .. } => {
-                            // this goes to master space only when target is not specified
-                            if request.target().is_empty() {
-                                *request.target_mut() = discrim.clone().immediate_parent().unwrap();
-                            }
-                        }
-                        // mark self as sender
-                        RequestContent::Message { sender, .. } => *sender = discrim.clone(),
-                        RequestContent::NewSpace { .. } | RequestContent::FocusAt => {}
+                        _ => {}
                     }
 
+                    // the rest go to spaces
+                    if let Err(error) = request.address(&discrim) {
+                        Self::reject(&canvas, &responder, error, Some(*request.id()));
+                        continue;
+                    }
+
+                    let canvas = canvas.clone();
                     let responder = responder.clone();
                     tokio::task::spawn(async move {
                         // otherwise, the request gets sended to the master space
                         // and starts propagating downwards
-                        let res = request.send().await;
+                        let res = request.send(&canvas).await;
 
                         // send a response to the request
                         // but requires no confirmation
@@ -302,6 +513,7 @@ impl Process {
         let _ = set_socket_recv.await;
 
         Ok(Self {
+            canvas,
             child,
             label,
             storage,
@@ -309,6 +521,8 @@ impl Process {
             discrim,
             command: [command].into_iter().chain(args).collect(),
             listener,
+            connector,
+            handler,
             responder,
             res: responder_send,
             confirm_handles,
@@ -328,35 +542,184 @@ impl Process {
                     target: target.clone(),
                     content: content.clone(),
                 };
-                let _ = packet.respond(Response::new_with_request(
+                let _ = packet.respond_with(
+                    &self.canvas,
                     ResponseContent::Success {
                         content: ResponseSuccess::MessageDelivered,
                     },
-                    *packet.get().id(),
-                ));
+                );
                 // unwraps the request, and pass to self as an event
                 // which will then get sent to the client as a normal event
                 let _ = self.pass(&mut event).await;
             }
             // spawn should be passed to spaces, no processes
             RequestContent::Spawn { .. } => {
-                let _ = packet.respond(Response::new_with_request(
-                    ResponseContent::Undelivered,
-                    *packet.get().id(),
-                ));
+                let _ = packet.respond_with(
+                    &self.canvas,
+                    ResponseContent::error(ResponseError::InvalidTarget),
+                );
             }
             // confirmreceive gets filtered out and handles in the listener loop
-            // so we will never get it
+            // the rest are meant for spaces
             RequestContent::ConfirmRecieve { .. }
+            | RequestContent::Hello { .. }
             | RequestContent::Unsubscribe { .. }
             | RequestContent::Drop { .. }
             | RequestContent::Subscribe { .. }
             | RequestContent::SetSocket { .. }
             | RequestContent::NewSpace { .. }
             | RequestContent::FocusAt
-            | RequestContent::Render { .. } => {
-                unreachable!("not a real request")
+            | RequestContent::Render { .. }
+            | RequestContent::GetScreenSize { .. }
+            | RequestContent::Get { .. }
+            | RequestContent::SetLayout { .. }
+            | RequestContent::PoolGet { .. }
+            | RequestContent::PoolSet { .. }
+            | RequestContent::PoolDelete { .. }
+            | RequestContent::PoolCompareAndSet { .. }
+            | RequestContent::PoolIncrement { .. }
+            | RequestContent::PoolDeleteIf { .. }
+            | RequestContent::PoolList { .. } => {
+                let _ = packet.respond_with(
+                    &self.canvas,
+                    ResponseContent::error(ResponseError::InvalidRequest),
+                );
+            }
+        }
+    }
+
+    /// read requests until the reader closes
+    /// requests are newline delimited json, until a handshake switches the encoding
+    fn read_requests(
+        canvas: Arc<Canvas>,
+        reader: impl AsyncRead + Unpin + Send + 'static,
+        requests: UnboundedSender<Request>,
+        responder: UnboundedSender<Response>,
+    ) {
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(reader);
+            let mut encoding = Encoding::Json;
+            let max_size = Limits::default().max_frame_size;
+
+            loop {
+                let frame = match encoding.read_frame_async(&mut reader, max_size).await {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => break,
+                    // the stream cannot be read past a bad frame, so the connection is closed
+                    Err(e) => {
+                        Self::reject(
+                            &canvas,
+                            &responder,
+                            ResponseError::ParseError {
+                                reason: e.to_string(),
+                            },
+                            None,
+                        );
+                        break;
+                    }
+                };
+
+                // reject bad frames, the connection is still fine
+                let request = match Request::decode(&frame, encoding) {
+                    Ok(req) => req,
+                    Err((error, id)) => {
+                        Self::reject(&canvas, &responder, error, id);
+                        continue;
+                    }
+                };
+
+                // the responder switches after responding to the handshake
+                if let Some(negotiated) = Self::handshake_encoding(&request) {
+                    encoding = negotiated;
+                }
+
+                if requests.send(request).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// write queued responses to a persistent connection until it closes
+    /// returns the queue, which is bounded so a component that stops reading
+    /// holds up its own responses and nothing else
+    fn write_responses(
+        canvas: Arc<Canvas>,
+        mut stream: Box<dyn AsyncWrite + Unpin + Send>,
+        confirm_handles: Arc<Mutex<HashMap<u32, oneshot::Sender<bool>>>>,
+        child: Arc<Mutex<Child>>,
+        discrim: Discriminator,
+    ) -> Sender<(u32, Vec<u8>)> {
+        let (send, mut recv): (Sender<(u32, Vec<u8>)>, _) = mpsc::channel(Self::QUEUED_RESPONSES);
+        tokio::spawn(async move {
+            while let Some((id, msg)) = recv.recv().await {
+                if stream.write_all(&msg).await.is_ok() {
+                    continue;
+                }
+
+                // nothing written to a closed connection gets confirmed
+                recv.close();
+                let mut confirm_handles = confirm_handles.lock().await;
+                confirm_handles.remove(&id);
+                while let Some((id, _)) = recv.recv().await {
+                    confirm_handles.remove(&id);
+                }
+                drop(confirm_handles);
+                Self::drop_if_exited(&canvas, &child, &discrim).await;
+                break;
             }
+        });
+        send
+    }
+
+    /// encoding a request switches to, if it is a handshake that will be accepted
+    /// both the reader and the handler go by this, so they never disagree
+    fn handshake_encoding(request: &Request) -> Option<Encoding> {
+        match request.content() {
+            RequestContent::Hello {
+                version, encoding, ..
+            } if is_compatible(*version) && *request.id() <= Limits::default().max_request_id => {
+                Some(Encoding::negotiate(*encoding))
+            }
+            _ => None,
+        }
+    }
+
+    /// stop all tasks of the process
+    fn abort(&self) {
+        self.responder.abort();
+        self.listener.abort();
+        self.connector.abort();
+        self.handler.abort();
+    }
+
+    /// respond with an error to a request that will not be handled
+    fn reject(
+        canvas: &Canvas,
+        responder: &UnboundedSender<Response>,
+        error: ResponseError,
+        request: Option<u32>,
+    ) {
+        let content = ResponseContent::error(error);
+        let _ = responder.send(match request {
+            Some(id) => Response::new_with_request(canvas, content, id),
+            None => Response::new(canvas, content),
+        });
+    }
+
+    /// tell the parent space to drop the process if it has exited
+    async fn drop_if_exited(canvas: &Canvas, child: &Mutex<Child>, discrim: &Discriminator) {
+        if child.lock().await.try_wait().unwrap().is_some() {
+            canvas.send(Event::RequestPacket(
+                Packet::new(Request::new(
+                    canvas,
+                    discrim.clone().immediate_parent().unwrap(),
+                    RequestContent::Drop {
+                        discrim: Some(discrim.clone()),
+                    },
+                ))
+                .0,
+            ))
         }
     }
 
@@ -398,20 +761,32 @@ impl Component for Process {
             return false.into();
         }
 
-        let resp = Response::new(ResponseContent::Event {
-            content: EventSerde::from_event(event),
-        });
+        let resp = Response::new(
+            &self.canvas,
+            ResponseContent::Event {
+                content: EventSerde::from_event(event),
+            },
+        );
 
         let rx = self.send_event(resp).await;
         // dont block
         // or else it will keep parent.processes locked
         Unevaluated::Unevaluated(tokio::spawn(async move { rx.await.unwrap_or(true) }))
     }
+
+    async fn exit(&self) {
+        self.abort();
+        let mut child = self.child.lock().await;
+        // kill the whole process group, not just the process itself
+        if let Some(pid) = child.id() {
+            unsafe { libc::kill(-(pid as libc::pid_t), libc::SIGKILL) };
+        }
+        let _ = child.kill().await;
+    }
 }
 
 impl Drop for Process {
     fn drop(&mut self) {
-        self.responder.abort();
-        self.listener.abort();
+        self.abort();
     }
 }
@@ -1,15 +1,21 @@
+use std::collections::HashMap;
 use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use tokio::sync::Mutex;
 
-use crate::traits::Component;
+use crate::traits::{Component, NativeComponent};
 
 use crate::structs::*;
 
 /// the basic unit of display
 pub struct Space {
+    /// the canvas the space is in
+    canvas: Arc<Canvas>,
+
     /// name of the current space
     label: String,
 
@@ -31,34 +37,65 @@ pub struct Space {
     /// process event subscriptions in this space
     passes: Arc<Mutex<Passes>>,
 
-    /// process pool
-    processes: Arc<Mutex<Collection<Process>>>,
-    // processes: Arc<Mutex<Collection<Process>>>,
+    /// processes and native components in this space
+    components: Arc<Mutex<Collection<dyn Component>>>,
+    /// off-screen cells drawn by components in this space
+    buffer: Arc<Mutex<Buffer>>,
+
+    /// where on the screen the space is, assigned by the parent space
+    region: Arc<Mutex<Rect>>,
+
+    /// how children are arranged in the region
+    layout: Arc<Mutex<Layout>>,
+
+    /// regions of children mentioned in the layout
+    regions: Arc<Mutex<HashMap<Discriminator, Rect>>>,
+
+    /// held while compositing, so frames are drawn in order
+    compositing: Mutex<()>,
 }
 
 impl Space {
-    pub async fn new(label: String) -> Self {
-        Self::new_with_parent(label, &Discriminator::default()).await
+    pub async fn new(canvas: Arc<Canvas>, label: String) -> Self {
+        let mut space = Self::new_with_parent(canvas, label, &Discriminator::default()).await;
+        // the master space takes up the whole screen
+        let (width, height) = space.canvas.size();
+        space.region = Arc::new(Mutex::new(Rect::new(0, 0, width, height)));
+        space
     }
 
     /// create new self with parent discriminator
-    async fn new_with_parent(label: String, parent_discrim: &Discriminator) -> Self {
+    async fn new_with_parent(
+        canvas: Arc<Canvas>,
+        label: String,
+        parent_discrim: &Discriminator,
+    ) -> Self {
         Self {
-            storage: Storage::new(parent_discrim).await,
+            storage: Storage::new(canvas.root(), parent_discrim).await,
             label,
-            discrim: parent_discrim.new_child(),
+            discrim: parent_discrim.new_child(canvas.discrim()),
+            canvas,
             pool: Pool::default(),
             subspaces: Arc::new(Mutex::new(Collection::default())),
             focus: Arc::new(Mutex::new(Focus::default())),
             passes: Arc::new(Mutex::new(Passes::default())),
-            processes: Arc::new(Mutex::new(Collection::default())),
+            components: Arc::new(Mutex::new(Collection::default())),
+            buffer: Arc::new(Mutex::new(Buffer::default())),
+            region: Arc::new(Mutex::new(Rect::default())),
+            layout: Arc::new(Mutex::new(Layout::default())),
+            regions: Arc::new(Mutex::new(HashMap::new())),
+            compositing: Mutex::new(()),
         }
     }
 
     /// start listening to all events, only the top level,
     /// "master" space should do this
     pub async fn listen(arc: Arc<Self>) {
-        let mut listener = Event::start();
+        let mut listener = match arc.canvas.listen() {
+            Some(listener) => listener,
+            // another space is already listening
+            None => return,
+        };
 
         while let Some(mut event) = listener.recv().await {
             // drop for quitting the entire application
@@ -76,7 +113,19 @@ impl Space {
             let arc = arc.clone();
             // pass the event to master space
             tokio::spawn(async move {
+                // layouts are updated before anyone gets the event
+                // so they can get their new regions straight away
+                if let Event::ScreenResize(width, height) = event {
+                    arc.set_region(Rect::new(0, 0, width, height)).await;
+                }
+
                 arc.pass(&mut event).await;
+
+                // the request has been handled by now
+                // so the frame is ready to be pushed
+                if event.redraws() {
+                    arc.composite().await;
+                }
             });
         }
     }
@@ -87,12 +136,386 @@ impl Space {
         label: String,
         command: String,
         args: Vec<String>,
+        transport: Transport,
     ) -> Result<(), Box<dyn Error>> {
-        self.processes
+        let process = Process::spawn(
+            self.canvas.clone(),
+            label,
+            &self.discrim,
+            command,
+            args,
+            transport,
+        )
+        .await?;
+        self.components.lock().await.insert_arc(Arc::new(process));
+        Ok(())
+    }
+
+    /// insert a native component, returns its discriminator
+    pub async fn add(&self, label: String, component: Arc<dyn NativeComponent>) -> Discriminator {
+        let native = Native::new(self.canvas.clone(), label, &self.discrim, component).await;
+        let discrim = native.discrim().clone();
+        self.components.lock().await.insert_arc(Arc::new(native));
+        discrim
+    }
+
+    /// find a space under self, or self
+    pub async fn find(self: &Arc<Self>, discrim: &Discriminator) -> Option<Arc<Self>> {
+        let mut space = self.clone();
+        while &space.discrim != discrim {
+            let child = space.discrim.immediate_child(discrim.clone())?;
+            let next = space.subspaces.lock().await.find_by_discrim_arc(&child)?;
+            space = next;
+        }
+        Some(space)
+    }
+
+    /// get a property of self or an immediate child
+    async fn get(&self, property: &Property, component: &Discriminator) -> ResponseContent {
+        // does not depend on component
+        if property == &Property::FocusedSpace {
+            let mut discrim = self.discrim.clone();
+            let mut focus = self.focus.lock().await.clone();
+            let mut subspaces = self.subspaces.clone();
+            while let Focus::Children(child) = focus {
+                let space = match subspaces.lock().await.find_by_discrim_arc(&child) {
+                    Some(space) => space,
+                    None => break,
+                };
+                discrim = child;
+                focus = space.focus.lock().await.clone();
+                subspaces = space.subspaces.clone();
+            }
+
+            return ResponseContent::Success {
+                content: ResponseSuccess::FocusedSpace { discrim },
+            };
+        }
+
+        let components = self.components.lock().await;
+        let subspaces = self.subspaces.lock().await;
+
+        let label = if component == &self.discrim {
+            self.label.clone()
+        } else if let Some(child) = components.find_by_discrim(component) {
+            child.label().to_string()
+        } else if let Some(space) = subspaces.find_by_discrim(component) {
+            space.label().to_string()
+        } else {
+            return ResponseContent::error(ResponseError::ComponentNotFound);
+        };
+
+        let content = match property {
+            Property::Discrim => ResponseSuccess::Discrim {
+                discrim: component.clone(),
+            },
+            Property::Label => ResponseSuccess::Label { label },
+            Property::Parent => ResponseSuccess::Parent {
+                discrim: component
+                    .clone()
+                    .immediate_parent()
+                    .filter(|parent| !parent.is_empty()),
+            },
+            // self is only asked about itself if it is the master space
+            // which has no siblings
+            Property::Siblings if component == &self.discrim => ResponseSuccess::Siblings {
+                siblings: Vec::new(),
+            },
+            Property::Siblings => ResponseSuccess::Siblings {
+                siblings: components
+                    .iter()
+                    .map(|child| child.discrim())
+                    .chain(subspaces.iter().map(Space::discrim))
+                    .filter(|discrim| discrim != &component)
+                    .cloned()
+                    .collect(),
+            },
+            Property::Region => ResponseSuccess::Region {
+                rect: self.region_of(Some(component)).await,
+            },
+            Property::Subscriptions => ResponseSuccess::Subscriptions {
+                subscriptions: self.passes.lock().await.subscriptions_of(component),
+            },
+            Property::FocusedSpace => unreachable!("handled above"),
+        };
+
+        ResponseContent::Success { content }
+    }
+
+    /// run a closure on the pool of self or an immediate child
+    /// along with the space the pool belongs to
+    async fn with_pool<T>(
+        &self,
+        component: &Discriminator,
+        f: impl FnOnce(&Pool, &Discriminator) -> T,
+    ) -> Option<T> {
+        if component == &self.discrim {
+            return Some(f(&self.pool, &self.discrim));
+        }
+
+        if let Some(child) = self.components.lock().await.find_by_discrim(component) {
+            return Some(f(child.pool(), &self.discrim));
+        }
+
+        self.subspaces
             .lock()
             .await
-            .insert(Process::spawn(label, &self.discrim, command, args).await?);
-        Ok(())
+            .find_by_discrim(component)
+            .map(|space| f(space.pool(), component))
+    }
+
+    /// handle a request to the pool of self or an immediate child
+    /// keys the sender cannot see are denied
+    async fn pool_request(&self, request: &RequestContent) -> ResponseContent {
+        let owner = match request.pool().and_then(|pool| pool.resolve(&self.discrim)) {
+            Some(owner) => owner,
+            None => return ResponseContent::error(ResponseError::InvalidRequest),
+        };
+
+        // the response, and the key with its scope, old and new value if it changed
+        let result = self
+            .with_pool(&owner, |pool, space| {
+                let access = PoolAccess {
+                    owner: &owner,
+                    space,
+                    component: request.sender(),
+                };
+
+                let (content, change) = match request {
+                    RequestContent::PoolGet { key, pointer, .. } => {
+                        let found = pool.get(&access, key, pointer)?;
+                        (
+                            ResponseSuccess::PoolValue {
+                                key: key.clone(),
+                                scope: found.as_ref().map(|(_, scope)| *scope),
+                                value: found.map(|(value, _)| value),
+                            },
+                            None,
+                        )
+                    }
+                    RequestContent::PoolSet {
+                        key,
+                        pointer,
+                        value,
+                        scope,
+                        ..
+                    } => {
+                        let edit = pool.set(&access, key, pointer, value.clone(), *scope)?;
+                        (
+                            ResponseSuccess::PoolSet {
+                                key: key.clone(),
+                                previous: edit.replaced,
+                            },
+                            Some((key, edit.scope, edit.old, edit.new)),
+                        )
+                    }
+                    RequestContent::PoolDelete { key, pointer, .. } => {
+                        let edit = pool.remove(&access, key, pointer)?;
+                        let change = edit
+                            .replaced
+                            .is_some()
+                            .then_some((key, edit.scope, edit.old, edit.new));
+                        (
+                            ResponseSuccess::PoolDeleted {
+                                key: key.clone(),
+                                value: edit.replaced,
+                            },
+                            change,
+                        )
+                    }
+                    RequestContent::PoolCompareAndSet {
+                        key,
+                        expected,
+                        value,
+                        scope,
+                        ..
+                    } => {
+                        let (success, value, scope) = pool.compare_and_set(
+                            &access,
+                            key,
+                            expected.as_ref(),
+                            value.clone(),
+                            *scope,
+                        )?;
+                        let change = success.then(|| (key, scope, expected.clone(), value.clone()));
+                        (
+                            ResponseSuccess::PoolCompared {
+                                key: key.clone(),
+                                success,
+                                value,
+                            },
+                            change,
+                        )
+                    }
+                    RequestContent::PoolIncrement { key, by, scope, .. } => {
+                        let (previous, value, scope) = pool.increment(&access, key, *by, *scope)?;
+                        (
+                            ResponseSuccess::PoolCounter {
+                                key: key.clone(),
+                                value,
+                            },
+                            Some((key, scope, previous, Some(value.into()))),
+                        )
+                    }
+                    RequestContent::PoolDeleteIf { key, expected, .. } => {
+                        let (success, value, scope) = pool.remove_if(&access, key, expected)?;
+                        let change = success.then(|| (key, scope, Some(expected.clone()), None));
+                        (
+                            ResponseSuccess::PoolCompared {
+                                key: key.clone(),
+                                success,
+                                value,
+                            },
+                            change,
+                        )
+                    }
+                    RequestContent::PoolList { prefix, .. } => (
+                        ResponseSuccess::PoolKeys {
+                            keys: pool.keys(&access, prefix),
+                        },
+                        None,
+                    ),
+                    _ => return Err(ResponseContent::error(ResponseError::InvalidRequest)),
+                };
+                Ok((content, change.map(|change| (space.clone(), change))))
+            })
+            .await;
+
+        let (content, change) = match result {
+            Some(Ok(result)) => result,
+            Some(Err(content)) => return content,
+            None => return ResponseContent::error(ResponseError::ComponentNotFound),
+        };
+
+        // let components watching the pool know, wherever they are
+        if let Some((space, (key, scope, old, new))) = change {
+            self.canvas.send(Event::PoolChange {
+                pool: owner,
+                space,
+                key: key.clone(),
+                scope,
+                old,
+                new,
+            });
+        }
+
+        content.into()
+    }
+
+    /// region of a child if it is in the layout, or the region of self otherwise
+    async fn region_of(&self, component: Option<&Discriminator>) -> Rect {
+        let region = match component {
+            Some(component) => self.regions.lock().await.get(component).copied(),
+            None => None,
+        };
+
+        match region {
+            Some(region) => region,
+            None => *self.region.lock().await,
+        }
+    }
+
+    /// change region of self and update regions of all children
+    pub async fn set_region(&self, region: Rect) {
+        self.move_to(region).await;
+        self.relayout().await;
+    }
+
+    /// change region of self only
+    /// the buffer is in screen coordinates, so what is drawn is cleared if it moved
+    async fn move_to(&self, region: Rect) {
+        let moved = std::mem::replace(&mut *self.region.lock().await, region) != region;
+        if moved {
+            self.buffer.lock().await.clear();
+        }
+    }
+
+    /// recompute regions of children, and their children
+    pub async fn relayout(&self) {
+        let mut queue = self.relayout_children().await;
+        while let Some(space) = queue.pop() {
+            queue.extend(space.relayout_children().await);
+        }
+    }
+
+    /// recompute regions of immediate children
+    /// returns subspaces, which should be relayouted next
+    async fn relayout_children(&self) -> Vec<Arc<Self>> {
+        let region = *self.region.lock().await;
+        let regions = self.layout.lock().await.compute(region);
+
+        let subspaces = self
+            .subspaces
+            .lock()
+            .await
+            .iter_arc()
+            .cloned()
+            .collect::<Vec<_>>();
+        for space in subspaces.iter() {
+            space
+                .move_to(regions.get(space.discrim()).copied().unwrap_or(region))
+                .await;
+        }
+
+        // components drew in their old regions, which are in the same buffer
+        let moved = std::mem::replace(&mut *self.regions.lock().await, regions.clone()) != regions;
+        if moved {
+            self.buffer.lock().await.clear();
+        }
+        subspaces
+    }
+
+    /// composite buffers of the visible spaces and draw it to the backend
+    /// does nothing if self is not the master space
+    pub async fn composite(&self) {
+        if self.discrim != Discriminator::master() {
+            return;
+        }
+
+        let _compositing = self.compositing.lock().await;
+        let (width, height) = self.canvas.size();
+        let mut frame = Buffer::new(width, height);
+        self.draw_onto(&mut frame).await;
+
+        self.canvas.backend().draw(&frame);
+    }
+
+    /// draw own buffer and visible children onto frame
+    fn draw_onto<'a>(
+        &'a self,
+        frame: &'a mut Buffer,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        Box::pin(async move {
+            let region = *self.region.lock().await;
+            frame.overlay(&*self.buffer.lock().await, region);
+
+            let layout = self.layout.lock().await.clone();
+            let focus = self.focus.lock().await.clone();
+
+            // stacked spaces only show the focused child
+            // tiled spaces show all children in the layout, with the focused one on top
+            let shown = {
+                let subspaces = self.subspaces.lock().await;
+                let mut shown = Vec::new();
+                if layout.is_tiled() {
+                    shown.extend(
+                        layout
+                            .children()
+                            .into_iter()
+                            .filter(|discrim| focus != Focus::Children((*discrim).clone()))
+                            .filter_map(|discrim| subspaces.find_by_discrim_arc(discrim)),
+                    );
+                }
+                if let Focus::Children(discrim) = &focus {
+                    shown.extend(subspaces.find_by_discrim_arc(discrim));
+                }
+                shown
+            };
+
+            for space in shown {
+                space.draw_onto(frame).await;
+            }
+        })
     }
 }
 
@@ -130,13 +553,11 @@ impl Component for Space {
                         if let Focus::Children(discrim) = &*focus {
                             #[cfg(feature = "log")]
                             log::debug!("{:?} locking subspaces", self.discrim);
-                            self.subspaces
-                                .lock()
-                                .await
-                                .find_by_discrim(discrim)
-                                .unwrap()
-                                .pass(&mut Event::Unfocus)
-                                .await;
+                            if let Some(space) =
+                                self.subspaces.lock().await.find_by_discrim(discrim)
+                            {
+                                space.pass(&mut Event::Unfocus).await;
+                            }
                             #[cfg(feature = "log")]
                             log::debug!("{:?} locked subspaces", self.discrim);
                             #[cfg(feature = "log")]
@@ -145,60 +566,80 @@ impl Component for Space {
                         }
                         #[cfg(feature = "log")]
                         log::debug!("{:?} unlocked focus", self.discrim);
-                        let _ = req.respond(Response::new_with_request(
+                        let _ = req.respond_with(
+                            &self.canvas,
                             ResponseContent::Success {
                                 content: ResponseSuccess::FocusChanged,
                             },
-                            *req.get().id(),
-                        ));
+                        );
 
                         return false.into();
                     }
                     RequestContent::NewSpace { label } => {
-                        let space = Space::new_with_parent(label.clone(), &self.discrim).await;
-                        let _ = req.respond(Response::new_with_request(
+                        let space = Space::new_with_parent(
+                            self.canvas.clone(),
+                            label.clone(),
+                            &self.discrim,
+                        )
+                        .await;
+                        let _ = req.respond_with(
+                            &self.canvas,
                             ResponseContent::Success {
                                 content: ResponseSuccess::SpaceCreated {
                                     discrim: space.discrim.clone(),
                                 },
                             },
-                            *req.get().id(),
-                        ));
+                        );
                         self.subspaces.lock().await.insert(space);
+                        self.relayout().await;
+                    }
+                    RequestContent::SetLayout { layout } => {
+                        *self.layout.lock().await = layout.clone();
+                        self.relayout().await;
+                        let _ = req.respond_with(
+                            &self.canvas,
+                            ResponseContent::Success {
+                                content: ResponseSuccess::LayoutSet,
+                            },
+                        );
                     }
                     // spawn a new process
                     RequestContent::Spawn {
                         command,
                         args,
                         label,
+                        transport,
                     } => {
                         // check if spawning process succeed
                         match Process::spawn(
+                            self.canvas.clone(),
                             label.clone(),
                             &self.discrim,
                             command.clone(),
                             args.clone(),
+                            *transport,
                         )
                         .await
                         {
                             Ok(process) => {
-                                let _ = req.respond(Response::new_with_request(
+                                let _ = req.respond_with(
+                                    &self.canvas,
                                     ResponseContent::Success {
                                         content: ResponseSuccess::Spawned {
                                             discrim: process.discrim().clone(),
                                         },
                                     },
-                                    *req.get().id(),
-                                ));
-                                self.processes.lock().await.insert(process);
+                                );
+                                self.components.lock().await.insert_arc(Arc::new(process));
                             }
-                            Err(_) => {
-                                let _ = req.respond(Response::new_with_request(
+                            Err(e) => {
+                                let _ = req.respond_with(
+                                    &self.canvas,
                                     ResponseContent::Error {
                                         content: ResponseError::SpawnFailed,
+                                        message: format!("failed to spawn {command}: {e}"),
                                     },
-                                    *req.get().id(),
-                                ));
+                                );
                             }
                         }
                     }
@@ -210,27 +651,30 @@ impl Component for Space {
                     } => {
                         // checks if the discrim is to a valid process
                         if let Some(child) = self.discrim.immediate_child(discrim.clone()) {
-                            if self.processes.lock().await.contains(&child) {
+                            if self.components.lock().await.contains(&child) {
                                 // if its a process, subscribe to the event right here
                                 self.passes.lock().await.subscribe(
                                     channel.clone(),
                                     PassItem::new(discrim.clone(), *priority),
                                 );
-                                let _ = req.respond(Response::new_with_request(
+                                let _ = req.respond_with(
+                                    &self.canvas,
                                     ResponseContent::Success {
                                         content: ResponseSuccess::SubscribeAdded,
                                     },
-                                    *req.get().id(),
-                                ));
+                                );
                             } else {
                                 // or else just throw a not found
-                                let _ = req.respond(Response::new_with_request(
-                                    ResponseContent::Error {
-                                        content: ResponseError::ComponentNotFound,
-                                    },
-                                    *req.get().id(),
-                                ));
+                                let _ = req.respond_with(
+                                    &self.canvas,
+                                    ResponseContent::error(ResponseError::ComponentNotFound),
+                                );
                             }
+                        } else {
+                            let _ = req.respond_with(
+                                &self.canvas,
+                                ResponseContent::error(ResponseError::InvalidTarget),
+                            );
                         }
                     }
                     // remove an item from
@@ -240,34 +684,38 @@ impl Component for Space {
                     } => {
                         // checks if the discrim is to a valid process
                         if let Some(child) = self.discrim.immediate_child(discrim.clone()) {
-                            if self.processes.lock().await.contains(&child) {
+                            if self.components.lock().await.contains(&child) {
                                 // if its a process, subscribe to the event right here
                                 self.passes
                                     .lock()
                                     .await
                                     .unsubscribe(channel.clone(), discrim);
-                                let _ = req.respond(Response::new_with_request(
+                                let _ = req.respond_with(
+                                    &self.canvas,
                                     ResponseContent::Success {
                                         content: ResponseSuccess::SubscribeRemoved,
                                     },
-                                    *req.get().id(),
-                                ));
+                                );
                             } else {
                                 // or else just throw a not found
-                                let _ = req.respond(Response::new_with_request(
-                                    ResponseContent::Error {
-                                        content: ResponseError::ComponentNotFound,
-                                    },
-                                    *req.get().id(),
-                                ));
+                                let _ = req.respond_with(
+                                    &self.canvas,
+                                    ResponseContent::error(ResponseError::ComponentNotFound),
+                                );
                             }
+                        } else {
+                            let _ = req.respond_with(
+                                &self.canvas,
+                                ResponseContent::error(ResponseError::InvalidTarget),
+                            );
                         }
                     }
-                    RequestContent::Drop { discrim } => {
+                    RequestContent::Drop {
+                        discrim: Some(discrim),
+                    } => {
                         // drop (remove) a child component
-                        if let Some(child) = self.discrim.immediate_child(discrim.clone().unwrap())
-                        {
-                            if self.processes.lock().await.remove(&child) {
+                        if let Some(child) = self.discrim.immediate_child(discrim.clone()) {
+                            if self.components.lock().await.remove(&child) {
                                 // if its a process, then remove all of its passes
                                 self.passes.lock().await.unsub_all(&child);
                             } else if self.subspaces.lock().await.remove(&child) {
@@ -277,32 +725,76 @@ impl Component for Space {
                                     *self.focus.lock().await = Focus::This
                                 }
                             } else {
-                                let _ = req.respond(Response::new_with_request(
-                                    ResponseContent::Error {
-                                        content: ResponseError::ComponentNotFound,
-                                    },
-                                    *req.get().id(),
-                                ));
+                                let _ = req.respond_with(
+                                    &self.canvas,
+                                    ResponseContent::error(ResponseError::ComponentNotFound),
+                                );
                                 return false.into();
                             }
-                            let _ = req.respond(Response::new_with_request(
+                            let _ = req.respond_with(
+                                &self.canvas,
                                 ResponseContent::Success {
                                     content: ResponseSuccess::Dropped,
                                 },
-                                *req.get().id(),
-                            ));
+                            );
+                        } else {
+                            let _ = req.respond_with(
+                                &self.canvas,
+                                ResponseContent::error(ResponseError::InvalidTarget),
+                            );
                         }
                     }
-                    RequestContent::Render { content, flush } => {
-                        // does rendering stuff, no explainations needed
-                        content.draw(*flush);
+                    RequestContent::Render {
+                        content, component, ..
+                    } => {
+                        // draws to own buffer in the region of the sender,
+                        // the master space will push it to screen if self is visible
+                        let region = self.region_of(component.as_ref()).await;
+                        let mut buffer = self.buffer.lock().await;
+                        let (width, height) = self.canvas.size();
+                        buffer.resize(width, height);
+                        content.draw(&mut buffer.view(region), &self.canvas);
+                        drop(buffer);
 
-                        let _ = req.respond(Response::new_with_request(
+                        let _ = req.respond_with(
+                            &self.canvas,
                             ResponseContent::Success {
                                 content: ResponseSuccess::Rendered,
                             },
-                            *req.get().id(),
-                        ));
+                        );
+                    }
+                    RequestContent::Get {
+                        property,
+                        component,
+                    } => {
+                        let content = self
+                            .get(property, component.as_ref().unwrap_or(&self.discrim))
+                            .await;
+                        let _ = req.respond_with(&self.canvas, content);
+                    }
+                    RequestContent::PoolGet { .. }
+                    | RequestContent::PoolSet { .. }
+                    | RequestContent::PoolDelete { .. }
+                    | RequestContent::PoolCompareAndSet { .. }
+                    | RequestContent::PoolIncrement { .. }
+                    | RequestContent::PoolDeleteIf { .. }
+                    | RequestContent::PoolList { .. } => {
+                        let content = self.pool_request(req.get().content()).await;
+                        let _ = req.respond_with(&self.canvas, content);
+                    }
+                    RequestContent::GetScreenSize { component } => {
+                        let (width, height) = self.canvas.size();
+                        let region = self.region_of(component.as_ref()).await;
+                        let _ = req.respond_with(
+                            &self.canvas,
+                            ResponseContent::Success {
+                                content: ResponseSuccess::ScreenSize {
+                                    width,
+                                    height,
+                                    region,
+                                },
+                            },
+                        );
                     }
                     RequestContent::Message {
                         content,
@@ -314,12 +806,12 @@ impl Component for Space {
                         let target = target.clone();
                         let content = content.clone();
 
-                        let _ = req.respond(Response::new_with_request(
+                        let _ = req.respond_with(
+                            &self.canvas,
                             ResponseContent::Success {
                                 content: ResponseSuccess::MessageDelivered,
                             },
-                            *req.get().id(),
-                        ));
+                        );
 
                         // now pass the event to self
                         *event = Event::Message {
@@ -330,14 +822,22 @@ impl Component for Space {
 
                         self.pass(event).await;
                     }
+                    // the component is filled in by the process, so spaces cannot tell
+                    // who these are for, and the rest are handled by processes themselves
                     RequestContent::Subscribe {
                         component: None, ..
                     }
                     | RequestContent::Unsubscribe {
                         component: None, ..
-                    } => unreachable!("impossible requests"),
-                    RequestContent::ConfirmRecieve { .. } | RequestContent::SetSocket { .. } => {
-                        unreachable!("not requests to spaces")
+                    }
+                    | RequestContent::Drop { discrim: None }
+                    | RequestContent::ConfirmRecieve { .. }
+                    | RequestContent::SetSocket { .. }
+                    | RequestContent::Hello { .. } => {
+                        let _ = req.respond_with(
+                            &self.canvas,
+                            ResponseContent::error(ResponseError::InvalidRequest),
+                        );
                     }
                 }
 
@@ -360,36 +860,33 @@ impl Component for Space {
                         #[cfg(feature = "log")]
                         log::debug!("{:?} locked subspaces", self.discrim);
 
-                        if !subspaces.contains(&child) {
-                            let _ = req.respond(Response::new_with_request(
-                                ResponseContent::Error {
-                                    content: ResponseError::ComponentNotFound,
-                                },
-                                *req.get().id(),
-                            ));
-                        }
+                        let subspace = match subspaces.find_by_discrim(&child) {
+                            Some(subspace) => subspace,
+                            None => {
+                                let _ = req.respond_with(
+                                    &self.canvas,
+                                    ResponseContent::error(ResponseError::ComponentNotFound),
+                                );
+                                return false.into();
+                            }
+                        };
 
                         if let Focus::Children(focused) = &*focus {
                             if req.get().target().starts_with(focused) {
-                                let subspace = subspaces.find_by_discrim_arc(&child).unwrap();
                                 subspace.pass(event).await;
                             } else {
-                                subspaces
-                                    .find_by_discrim(focused)
-                                    .unwrap()
-                                    .pass(&mut Event::Unfocus)
-                                    .await;
+                                if let Some(focused) = subspaces.find_by_discrim(focused) {
+                                    focused.pass(&mut Event::Unfocus).await;
+                                }
                                 *focus = Focus::Children(child.clone());
 
-                                let child = subspaces.find_by_discrim(&child).unwrap();
-                                child.pass(event).await;
-                                child.pass(&mut Event::Focus).await;
+                                subspace.pass(event).await;
+                                subspace.pass(&mut Event::Focus).await;
                             }
                         } else {
                             *focus = Focus::Children(child.clone());
-                            let child = subspaces.find_by_discrim(&child).unwrap();
-                            child.pass(event).await;
-                            child.pass(&mut Event::Focus).await;
+                            subspace.pass(event).await;
+                            subspace.pass(&mut Event::Focus).await;
                         }
 
                         #[cfg(feature = "log")]
@@ -401,7 +898,7 @@ impl Component for Space {
                     }
 
                     // no 2 components are the same, so order shouldnt matter
-                    if let Some(proc) = self.processes.lock().await.find_by_discrim(&child) {
+                    if let Some(proc) = self.components.lock().await.find_by_discrim(&child) {
                         if let Some(subscriptions) = req.get().subscriptions() {
                             if self
                                 .passes
@@ -413,10 +910,8 @@ impl Component for Space {
                             {
                                 proc.pass(event).await;
                             } else {
-                                let _ = req.respond(Response::new_with_request(
-                                    ResponseContent::Undelivered,
-                                    *req.get().id(),
-                                ));
+                                let _ =
+                                    req.respond_with(&self.canvas, ResponseContent::Undelivered);
                             }
                         } else {
                             proc.pass(event).await;
@@ -426,21 +921,53 @@ impl Component for Space {
                     {
                         space.pass(event).await;
                     } else {
-                        let _ = req.respond(Response::new(ResponseContent::Undelivered));
+                        let _ = req.respond_with(
+                            &self.canvas,
+                            ResponseContent::error(ResponseError::ComponentNotFound),
+                        );
                     }
 
                     return false.into();
                 }
+
                 // otherwise self is not a parent to the target component
-                // and something went wrong
+                // and the request can go nowhere
+                let _ = req.respond_with(
+                    &self.canvas,
+                    ResponseContent::error(ResponseError::InvalidTarget),
+                );
+                return false.into();
             }
             _ => {}
         }
 
         // all components listening to this event
-        let targets = self.passes.lock().await.subscribers(&event.subscriptions());
+        let mut targets = self.passes.lock().await.subscribers(&event.subscriptions());
 
-        let processes = self.processes.clone();
+        // changes to keys are only for components that can see them
+        if let Event::PoolChange {
+            pool, space, scope, ..
+        } = event
+        {
+            targets.retain(|target| scope.visible(pool, space, target.discrim()));
+        }
+
+        let region = *self.region.lock().await;
+        let regions = self.regions.lock().await.clone();
+        let layout = self.layout.lock().await.clone();
+        if let Event::MouseEvent(mouse) = event {
+            // only components under the pointer get mouse events,
+            // those with their own region in the layout get them first
+            targets.retain(|target| {
+                regions
+                    .get(target.discrim())
+                    .unwrap_or(&region)
+                    .contains(mouse.x(), mouse.y())
+            });
+            targets.sort_by_key(|target| !regions.contains_key(target.discrim()));
+        }
+
+        let components = self.components.clone();
         let mut event = event.clone();
         let subspaces = self.subspaces.clone();
         let focus = self.focus.clone();
@@ -451,13 +978,23 @@ impl Component for Space {
             for target in targets {
                 #[cfg(feature = "log")]
                 log::debug!("passing {event:?} to {target:?}");
-                let res = processes
+                // mouse coordinates are relative to the region of the process
+                let mut local = match &event {
+                    Event::MouseEvent(mouse) => Event::MouseEvent(
+                        mouse.relative_to(regions.get(target.discrim()).unwrap_or(&region)),
+                    ),
+                    event => event.clone(),
+                };
+                // the component may have been dropped since
+                let component = match components
                     .lock()
                     .await
                     .find_by_discrim_arc(target.discrim())
-                    .unwrap()
-                    .pass(&mut event)
-                    .await;
+                {
+                    Some(component) => component,
+                    None => continue,
+                };
+                let res = component.pass(&mut local).await;
                 let res = res.evaluate().await;
                 if !res {
                     #[cfg(feature = "log")]
@@ -470,22 +1007,55 @@ impl Component for Space {
                 return false;
             }
 
+            // pool changes are for everyone watching, not just the focused space
+            if let Event::PoolChange { .. } = event {
+                let subspaces: Vec<Arc<Space>> =
+                    subspaces.lock().await.iter_arc().cloned().collect();
+                for subspace in subspaces {
+                    subspace.pass(&mut event.clone()).await.evaluate().await;
+                }
+                return true;
+            }
+
             // if all went well then continue to pass down into subspaces
             #[cfg(feature = "log")]
             log::debug!("{:?} locking focus", discrim);
-            let focus = focus.lock().await.clone();
+            let mut focus = focus.lock().await.clone();
             #[cfg(feature = "log")]
             log::debug!("{:?} locked focus", discrim);
             #[cfg(feature = "log")]
             log::debug!("{:?} unlocked focus", discrim);
+
+            // in tiled layouts, mouse events go to the space under the pointer
+            // even if it is not focused
+            if let (Event::MouseEvent(mouse), true) = (&event, layout.is_tiled()) {
+                let under_focus = match &focus {
+                    Focus::Children(discrim) => regions
+                        .get(discrim)
+                        .unwrap_or(&region)
+                        .contains(mouse.x(), mouse.y()),
+                    Focus::This => false,
+                };
+
+                if !under_focus {
+                    let subspaces = subspaces.lock().await;
+                    if let Some(discrim) = layout
+                        .hit_test(&regions, mouse.x(), mouse.y())
+                        .into_iter()
+                        .find(|discrim| subspaces.contains(discrim))
+                    {
+                        focus = Focus::Children(discrim.clone());
+                    }
+                }
+            }
+
             if let Focus::Children(discrim) = focus {
                 #[cfg(feature = "log")]
                 log::debug!("{:?} locking subspaces", discrim);
-                let subspace = subspaces
-                    .lock()
-                    .await
-                    .find_by_discrim_arc(&discrim)
-                    .unwrap();
+                let subspace = match subspaces.lock().await.find_by_discrim_arc(&discrim) {
+                    Some(subspace) => subspace,
+                    None => return true,
+                };
                 #[cfg(feature = "log")]
                 log::debug!("{:?} locked subspaces", discrim);
                 #[cfg(feature = "log")]
@@ -500,4 +1070,17 @@ impl Component for Space {
 
         Unevaluated::Unevaluated(uneval)
     }
+
+    async fn exit(&self) {
+        let components: Vec<Arc<dyn Component>> =
+            self.components.lock().await.iter_arc().cloned().collect();
+        for component in components {
+            component.exit().await;
+        }
+
+        let subspaces: Vec<Arc<Space>> = self.subspaces.lock().await.iter_arc().cloned().collect();
+        for subspace in subspaces {
+            subspace.exit().await;
+        }
+    }
 }
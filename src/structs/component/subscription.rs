@@ -1,11 +1,12 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::structs::{KeyCode, KeyEvent, KeyModifier, MouseType};
+use crate::structs::{KeyCode, KeyEvent, KeyModifier, MouseType, PoolTarget, ResponseError};
 
 use super::Discriminator;
 
 /// a single subscription item, such as a key press event
-#[derive(Hash, PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
+#[derive(Hash, PartialEq, Eq, Serialize, Deserialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "type")]
 pub enum Subscription {
     /// subscribes to all key press events
@@ -42,9 +43,42 @@ pub enum Subscription {
     /// current space unfocused
     Unfocused,
 
+    #[serde(rename = "pool change")]
+    /// a key in a pool is set or removed
+    /// or any key starting with key if prefix is true
+    PoolChange {
+        #[serde(default)]
+        pool: PoolTarget,
+        key: String,
+        #[serde(default)]
+        prefix: bool,
+    },
+
     #[serde(rename = "multiple")]
     /// subscribe to multiple channels at once
     Multiple {
         subs: Vec<(Subscription, Option<u32>)>,
     },
 }
+
+impl Subscription {
+    /// point pool subscriptions at the component owning the pool
+    /// as they are relative to the sender
+    pub fn resolve_pools(&mut self, sender: &Discriminator) -> Result<(), ResponseError> {
+        match self {
+            Self::PoolChange { pool, .. } => {
+                *pool = PoolTarget::Component {
+                    discrim: pool.resolve(sender).ok_or(ResponseError::InvalidTarget)?,
+                };
+            }
+            Self::Multiple { subs } => {
+                for (sub, _) in subs {
+                    sub.resolve_pools(sender)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// how a process sends requests and recieves responses
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default, JsonSchema)]
+#[serde(tag = "type")]
+pub enum Transport {
+    /// through unix sockets in the storage folder
+    #[serde(rename = "socket")]
+    #[default]
+    Socket,
+    /// newline delimited requests from stdout, and responses to stdin
+    #[serde(rename = "stdio")]
+    Stdio,
+}
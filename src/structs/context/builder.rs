@@ -0,0 +1,189 @@
+use std::{
+    error::Error,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use tokio::fs;
+
+use crate::{
+    structs::{Canvas, Feature, Space, Storage, Transport},
+    term::TerminalBackend,
+    traits::{Backend, NativeComponent},
+};
+
+use super::CanvasHandle;
+
+/// a component to add to the master space when the canvas starts
+enum InitialComponent {
+    Process {
+        label: String,
+        command: String,
+        args: Vec<String>,
+        transport: Transport,
+    },
+    Native {
+        label: String,
+        component: Arc<dyn NativeComponent>,
+    },
+}
+
+/// options for creating a canvas, from `Canvas::builder()`
+pub struct CanvasBuilder {
+    /// storage folder, a folder in /tmp/ccanvas if not specified
+    root: Option<PathBuf>,
+
+    /// backend to draw to, the terminal if not specified
+    backend: Option<Box<dyn Backend>>,
+
+    /// processes and native components in the master space
+    components: Vec<InitialComponent>,
+
+    /// protocol features components are allowed to use
+    features: Vec<Feature>,
+}
+
+impl Default for CanvasBuilder {
+    fn default() -> Self {
+        Self {
+            root: None,
+            backend: None,
+            components: Vec::new(),
+            features: Feature::SUPPORTED.to_vec(),
+        }
+    }
+}
+
+impl CanvasBuilder {
+    /// keep storage of all components in this folder
+    /// it is removed on exit, so it must be empty or not exist
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// draw to and take input from this backend instead of the terminal
+    pub fn backend(mut self, backend: impl Backend + 'static) -> Self {
+        self.backend = Some(Box::new(backend));
+        self
+    }
+
+    /// spawn a process in the master space when the canvas starts
+    pub fn spawn(
+        mut self,
+        label: impl Into<String>,
+        command: impl Into<String>,
+        args: Vec<String>,
+        transport: Transport,
+    ) -> Self {
+        self.components.push(InitialComponent::Process {
+            label: label.into(),
+            command: command.into(),
+            args,
+            transport,
+        });
+        self
+    }
+
+    /// add a native component to the master space when the canvas starts
+    pub fn component(
+        mut self,
+        label: impl Into<String>,
+        component: impl NativeComponent + 'static,
+    ) -> Self {
+        self.components.push(InitialComponent::Native {
+            label: label.into(),
+            component: Arc::new(component),
+        });
+        self
+    }
+
+    /// stop components from using a protocol feature
+    /// requests that depend on it are rejected
+    pub fn disable(mut self, feature: Feature) -> Self {
+        self.features.retain(|enabled| enabled != &feature);
+        self
+    }
+
+    /// create the canvas and start listening to events
+    pub async fn build(self) -> Result<CanvasHandle, Box<dyn Error>> {
+        let root = match self.root {
+            Some(root) => {
+                if fs::try_exists(&root).await?
+                    && fs::read_dir(&root).await?.next_entry().await?.is_some()
+                {
+                    return Err(io::Error::new(
+                        ErrorKind::AlreadyExists,
+                        format!("{} is not empty", root.display()),
+                    )
+                    .into());
+                }
+                root
+            }
+            None => {
+                let root = Canvas::default_root();
+                Storage::remove_if_exist(&root).await?;
+                root
+            }
+        };
+        fs::create_dir_all(&root).await?;
+
+        let backend = match self.backend {
+            Some(backend) => backend,
+            None => Box::new(TerminalBackend::new()?),
+        };
+
+        #[cfg(feature = "log")]
+        {
+            let log_file = dirs::data_dir().unwrap().join("ccanvas.log");
+            // only the first canvas sets up logging
+            let _ = simplelog::WriteLogger::init(
+                log::LevelFilter::Trace,
+                simplelog::ConfigBuilder::new()
+                    .set_max_level(log::LevelFilter::Trace)
+                    .set_location_level(log::LevelFilter::Trace)
+                    .build(),
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .open(log_file)
+                    .unwrap(),
+            );
+        }
+
+        let canvas = Canvas::new(root, backend, self.features);
+        let master = Arc::new(Space::new(canvas.clone(), "master".to_string()).await);
+        let listener = tokio::spawn(Space::listen(master.clone()));
+        let handle = CanvasHandle::new(canvas, master, listener);
+
+        for component in self.components {
+            let (label, command, args, transport) = match component {
+                InitialComponent::Process {
+                    label,
+                    command,
+                    args,
+                    transport,
+                } => (label, command, args, transport),
+                InitialComponent::Native { label, component } => {
+                    handle.master().add(label, component).await;
+                    continue;
+                }
+            };
+
+            let spawned = handle
+                .master()
+                .spawn(label, command.clone(), args, transport)
+                .await
+                .map_err(|e| format!("failed to spawn {command}: {e}"));
+
+            if let Err(message) = spawned {
+                handle.shutdown().await;
+                return Err(message.into());
+            }
+        }
+
+        Ok(handle)
+    }
+}
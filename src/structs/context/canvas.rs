@@ -0,0 +1,148 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::{
+    structs::{Event, Feature},
+    traits::Backend,
+};
+
+use super::CanvasBuilder;
+
+/// number of canvases created in this process, used to give each its own folder
+static CANVASES: AtomicU32 = AtomicU32::new(0);
+
+/// state shared by everything in a single canvas
+///
+/// every space and process holds on to the canvas it is in,
+/// so multiple canvases can run side by side
+pub struct Canvas {
+    /// sender to the event stream of the master space
+    events: UnboundedSender<Event>,
+
+    /// reciever of the event stream, taken by the master space
+    listener: Mutex<Option<UnboundedReceiver<Event>>>,
+
+    /// where frames are drawn to and input comes from
+    backend: Mutex<Box<dyn Backend>>,
+
+    /// folder containing storage of all components
+    root: PathBuf,
+
+    /// protocol features components are allowed to use
+    features: Vec<Feature>,
+
+    /// last discriminator chunk given out
+    discrim: AtomicU32,
+
+    /// last id given to a request generated by ccanvas, counting down
+    request_id: AtomicU32,
+
+    /// last id given to a response
+    response_id: AtomicU32,
+}
+
+impl Canvas {
+    /// configure a new canvas
+    pub fn builder() -> CanvasBuilder {
+        CanvasBuilder::default()
+    }
+
+    /// storage folder used if none is specified
+    /// a different one for each canvas in this process
+    pub(super) fn default_root() -> PathBuf {
+        PathBuf::from("/tmp")
+            .join("ccanvas")
+            .join(match CANVASES.fetch_add(1, Ordering::Relaxed) {
+                0 => process::id().to_string(),
+                n => format!("{}-{n}", process::id()),
+            })
+    }
+
+    /// create a canvas drawing to a backend, the storage folder should already exist
+    pub(super) fn new(
+        root: PathBuf,
+        backend: Box<dyn Backend>,
+        features: Vec<Feature>,
+    ) -> Arc<Self> {
+        let (events, listener) = mpsc::unbounded_channel();
+
+        Arc::new(Self {
+            events,
+            listener: Mutex::new(Some(listener)),
+            backend: Mutex::new(backend),
+            root,
+            features,
+            discrim: AtomicU32::new(0),
+            request_id: AtomicU32::new(u32::MAX),
+            response_id: AtomicU32::new(0),
+        })
+    }
+
+    /// take the event stream and start recieving input from the backend
+    /// only the master space should do this, once
+    pub fn listen(&self) -> Option<UnboundedReceiver<Event>> {
+        let listener = self.listener.lock().unwrap().take()?;
+        self.backend().listen(self.events.clone());
+        Some(listener)
+    }
+
+    /// send an event to the main event stream
+    pub fn send(&self, event: Event) {
+        let _ = self.events.send(event);
+    }
+
+    /// the backend of the canvas
+    pub fn backend(&self) -> MutexGuard<'_, Box<dyn Backend>> {
+        self.backend.lock().unwrap()
+    }
+
+    /// returns current screen size as (width, height)
+    pub fn size(&self) -> (u32, u32) {
+        self.backend().size()
+    }
+
+    /// folder containing storage of all components
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// protocol features components are allowed to use
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// if components are allowed to use a feature
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// get a unique discriminator chunk
+    pub fn discrim(&self) -> u32 {
+        self.discrim.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// get a unique id for a request generated by ccanvas
+    pub fn request_id(&self) -> u32 {
+        self.request_id.fetch_sub(1, Ordering::Relaxed) - 1
+    }
+
+    /// get a unique id for a response
+    pub fn response_id(&self) -> u32 {
+        self.response_id.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// restore the backend and remove all storage
+    pub fn exit(&self) {
+        self.backend().exit();
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
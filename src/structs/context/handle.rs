@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+
+use crate::{
+    structs::{
+        Canvas, Discriminator, Event, Request, RequestContent, Response, ResponseContent,
+        ResponseError, ResponseSuccess, Space, Transport,
+    },
+    traits::{Component, NativeComponent},
+};
+
+/// a running canvas, returned by `CanvasBuilder::build()`
+pub struct CanvasHandle {
+    /// the canvas everything is in
+    canvas: Arc<Canvas>,
+
+    /// the top level space
+    master: Arc<Space>,
+
+    /// task passing events to the master space
+    listener: JoinHandle<()>,
+}
+
+impl CanvasHandle {
+    pub(super) fn new(canvas: Arc<Canvas>, master: Arc<Space>, listener: JoinHandle<()>) -> Self {
+        Self {
+            canvas,
+            master,
+            listener,
+        }
+    }
+
+    /// the canvas everything is in
+    pub fn canvas(&self) -> &Arc<Canvas> {
+        &self.canvas
+    }
+
+    /// the top level space
+    pub fn master(&self) -> &Arc<Space> {
+        &self.master
+    }
+
+    /// spawn a process in a space, returns the discriminator of the process
+    /// or the response if it could not be spawned
+    pub async fn spawn(
+        &self,
+        space: Discriminator,
+        label: impl Into<String>,
+        command: impl Into<String>,
+        args: Vec<String>,
+        transport: Transport,
+    ) -> Result<Discriminator, ResponseContent> {
+        let response = self
+            .request(
+                space,
+                RequestContent::Spawn {
+                    command: command.into(),
+                    args,
+                    label: label.into(),
+                    transport,
+                },
+            )
+            .await;
+
+        match response.content() {
+            ResponseContent::Success {
+                content: ResponseSuccess::Spawned { discrim },
+            } => Ok(discrim.clone()),
+            content => Err(content.clone()),
+        }
+    }
+
+    /// add a native component to a space, returns the discriminator of the component
+    pub async fn add(
+        &self,
+        space: &Discriminator,
+        label: impl Into<String>,
+        component: impl NativeComponent + 'static,
+    ) -> Result<Discriminator, ResponseError> {
+        match self.master.find(space).await {
+            Some(space) => Ok(space.add(label.into(), Arc::new(component)).await),
+            None => Err(ResponseError::ComponentNotFound),
+        }
+    }
+
+    /// send a request to a component and wait for its response
+    pub async fn request(&self, target: Discriminator, content: RequestContent) -> Response {
+        Request::new(&self.canvas, target, content)
+            .send(&self.canvas)
+            .await
+    }
+
+    /// send an event as if it came from the backend
+    pub fn send(&self, event: Event) {
+        self.canvas.send(event);
+    }
+
+    /// wait until a component quits the canvas, then shut it down
+    pub async fn wait(mut self) {
+        let _ = (&mut self.listener).await;
+        self.exit().await;
+    }
+
+    /// kill all components, restore the backend and remove all storage
+    pub async fn shutdown(mut self) {
+        self.listener.abort();
+        let _ = (&mut self.listener).await;
+        self.exit().await;
+    }
+
+    /// clean up after the listener has stopped
+    /// components are stopped explicitly, as tasks may still hold on to them
+    async fn exit(self) {
+        self.master.exit().await;
+        self.canvas.exit();
+    }
+}
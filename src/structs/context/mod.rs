@@ -0,0 +1,8 @@
+mod canvas;
+pub use canvas::*;
+
+mod builder;
+pub use builder::*;
+
+mod handle;
+pub use handle::*;
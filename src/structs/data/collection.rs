@@ -5,12 +5,12 @@ use crate::structs::Discriminator;
 use crate::traits::Component;
 
 /// a collection of component items
-pub struct Collection<T: Component> {
+pub struct Collection<T: Component + ?Sized> {
     // items: HashMap<Discriminator, Arc<Mutex<T>>>,
     items: HashMap<Discriminator, Arc<T>>,
 }
 
-impl<T: Component> Collection<T> {
+impl<T: Component + ?Sized> Collection<T> {
     /// return all elements with that label
     pub async fn find_all_by_label(&self, label: &str) -> Vec<&T> {
         self.items
@@ -40,14 +40,24 @@ impl<T: Component> Collection<T> {
     //     self.items.get_mut(discrim)
     // }
 
+    /// iterate over all items
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.values().map(|item| item.as_ref())
+    }
+
+    /// iterate over all items, returning arcs
+    pub fn iter_arc(&self) -> impl Iterator<Item = &Arc<T>> {
+        self.items.values()
+    }
+
     /// check if the item is in collection
     pub fn contains(&self, discrim: &Discriminator) -> bool {
         self.items.contains_key(discrim)
     }
 
-    /// insert an item and return handle to it
-    pub fn insert(&mut self, item: T) {
-        self.items.insert(item.discrim().clone(), Arc::new(item));
+    /// insert an item that is already in an arc
+    pub fn insert_arc(&mut self, item: Arc<T>) {
+        self.items.insert(item.discrim().clone(), item);
     }
 
     /// removes an item by discrim
@@ -56,7 +66,14 @@ impl<T: Component> Collection<T> {
     }
 }
 
-impl<T: Component> Default for Collection<T> {
+impl<T: Component> Collection<T> {
+    /// insert an item and return handle to it
+    pub fn insert(&mut self, item: T) {
+        self.insert_arc(Arc::new(item));
+    }
+}
+
+impl<T: Component + ?Sized> Default for Collection<T> {
     fn default() -> Self {
         Self {
             items: HashMap::default(),
@@ -1,5 +1,7 @@
 use tokio::sync::oneshot;
 
+use crate::structs::{Canvas, Request, Response, ResponseContent};
+
 /// a packet of info, expecting response
 #[derive(Debug)]
 pub struct Packet<T, R> {
@@ -41,6 +43,18 @@ impl<T, R> Packet<T, R> {
     }
 }
 
+impl Packet<Request, Response> {
+    /// respond to the request with content
+    pub fn respond_with(
+        &mut self,
+        canvas: &Canvas,
+        content: ResponseContent,
+    ) -> Result<(), crate::Error> {
+        let response = Response::new_with_request(canvas, content, *self.get().id());
+        self.respond(response)
+    }
+}
+
 impl<T, R> PartialEq for Packet<T, R> {
     fn eq(&self, _other: &Self) -> bool {
         false // no 2 packets are the same
@@ -1,7 +1,637 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::mem;
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+use crate::structs::{Discriminator, PoolScope, ResponseContent, ResponseError};
 
 /// pool of key-value pairs for shared or private access
+/// a component can read and write every key it can see
 #[derive(Default)]
 pub struct Pool {
-    map: HashMap<String, String>,
+    map: Mutex<HashMap<String, Entry>>,
+}
+
+/// a value in the pool, and who can see it
+struct Entry {
+    value: Value,
+    scope: PoolScope,
+}
+
+/// the component accessing a pool
+pub struct PoolAccess<'a> {
+    /// owner of the pool
+    pub owner: &'a Discriminator,
+    /// space the pool belongs to
+    pub space: &'a Discriminator,
+    /// component accessing the pool, None for ccanvas itself
+    pub component: Option<&'a Discriminator>,
+}
+
+impl PoolAccess<'_> {
+    /// if the component can see a key with the scope
+    pub fn sees(&self, scope: &PoolScope) -> bool {
+        self.component
+            .is_none_or(|component| scope.visible(self.owner, self.space, component))
+    }
+
+    /// if the component can set the scope of keys
+    pub fn owns(&self) -> bool {
+        self.component
+            .is_none_or(|component| component == self.owner)
+    }
+
+    /// error if there is a key the component cannot see
+    fn check(&self, entry: Option<&Entry>) -> Result<(), ResponseContent> {
+        match entry {
+            Some(entry) if !self.sees(&entry.scope) => {
+                Err(ResponseContent::error(ResponseError::PermissionDenied))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// the tightest scope the component can still see
+    fn tightest(&self) -> PoolScope {
+        PoolScope::ALL
+            .into_iter()
+            .find(|scope| self.sees(scope))
+            .unwrap_or(PoolScope::Global)
+    }
+
+    /// scope of a key after it is written to
+    /// only the owner sets scopes, other keys keep theirs
+    /// or get the tightest scope their creator can see
+    fn scope(
+        &self,
+        entry: Option<&Entry>,
+        scope: Option<PoolScope>,
+    ) -> Result<PoolScope, ResponseContent> {
+        match (entry, scope) {
+            (_, Some(scope)) if self.owns() => Ok(scope),
+            (Some(entry), None) => Ok(entry.scope),
+            (None, None) => Ok(self.tightest()),
+            (_, Some(_)) => Err(ResponseContent::Error {
+                content: ResponseError::PermissionDenied,
+                message: "only the owner of a pool can set the scope of a key".to_string(),
+            }),
+        }
+    }
+}
+
+/// how the value of a key is changed by a write
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolEdit {
+    /// value at the pointer that got replaced or removed
+    pub replaced: Option<Value>,
+    /// value of the whole key before the write
+    pub old: Option<Value>,
+    /// value of the whole key after the write
+    pub new: Option<Value>,
+    /// scope of the key after the write, or before it is removed
+    pub scope: PoolScope,
+}
+
+impl Pool {
+    /// value at a json pointer in a key, an empty pointer for the whole value
+    /// along with the scope of the key
+    pub fn get(
+        &self,
+        access: &PoolAccess,
+        key: &str,
+        pointer: &str,
+    ) -> Result<Option<(Value, PoolScope)>, ResponseContent> {
+        // a bad pointer would look like the value is not set
+        check_pointer(pointer)?;
+        let map = self.map.lock().unwrap();
+        let entry = map.get(key);
+        access.check(entry)?;
+        Ok(entry.and_then(|entry| Some((entry.value.pointer(pointer)?.clone(), entry.scope))))
+    }
+
+    /// set the value at a json pointer in a key, an empty pointer for the whole value
+    /// a key has to be set before anything inside it can be
+    pub fn set(
+        &self,
+        access: &PoolAccess,
+        key: &str,
+        pointer: &str,
+        value: Value,
+        scope: Option<PoolScope>,
+    ) -> Result<PoolEdit, ResponseContent> {
+        let mut map = self.map.lock().unwrap();
+        access.check(map.get(key))?;
+        let scope = access.scope(map.get(key), scope)?;
+        let old = map.get(key).map(|entry| entry.value.clone());
+
+        let replaced = if pointer.is_empty() {
+            map.insert(key.to_string(), Entry { value, scope })
+                .map(|entry| entry.value)
+        } else {
+            let entry = map
+                .get_mut(key)
+                .ok_or_else(|| invalid(format!("{key} is not set")))?;
+            let replaced = set_at(&mut entry.value, pointer, value).map_err(invalid)?;
+            entry.scope = scope;
+            replaced
+        };
+
+        Ok(PoolEdit {
+            replaced,
+            old,
+            new: map.get(key).map(|entry| entry.value.clone()),
+            scope,
+        })
+    }
+
+    /// remove the value at a json pointer in a key, an empty pointer for the whole key
+    pub fn remove(
+        &self,
+        access: &PoolAccess,
+        key: &str,
+        pointer: &str,
+    ) -> Result<PoolEdit, ResponseContent> {
+        check_pointer(pointer)?;
+        let mut map = self.map.lock().unwrap();
+        access.check(map.get(key))?;
+        let old = map.get(key).map(|entry| entry.value.clone());
+        let scope = map.get(key).map(|entry| entry.scope).unwrap_or_default();
+
+        let replaced = if pointer.is_empty() {
+            map.remove(key).map(|entry| entry.value)
+        } else {
+            match map.get_mut(key) {
+                Some(entry) => remove_at(&mut entry.value, pointer).map_err(invalid)?,
+                None => None,
+            }
+        };
+
+        Ok(PoolEdit {
+            replaced,
+            old,
+            new: map.get(key).map(|entry| entry.value.clone()),
+            scope,
+        })
+    }
+
+    /// set a key only if its current value is expected, None for not set
+    /// returns if it is set, the value it ends up with and its scope
+    pub fn compare_and_set(
+        &self,
+        access: &PoolAccess,
+        key: &str,
+        expected: Option<&Value>,
+        value: Value,
+        scope: Option<PoolScope>,
+    ) -> Result<(bool, Option<Value>, PoolScope), ResponseContent> {
+        let mut map = self.map.lock().unwrap();
+        let entry = map.get(key);
+        access.check(entry)?;
+        let current = entry.map(|entry| &entry.value);
+        if current != expected {
+            let scope = entry.map(|entry| entry.scope).unwrap_or_default();
+            return Ok((false, current.cloned(), scope));
+        }
+
+        let scope = access.scope(entry, scope)?;
+        map.insert(
+            key.to_string(),
+            Entry {
+                value: value.clone(),
+                scope,
+            },
+        );
+        Ok((true, Some(value), scope))
+    }
+
+    /// add to the integer value of a key, which counts as 0 if not set
+    /// returns the previous and new value, and the scope of the key
+    pub fn increment(
+        &self,
+        access: &PoolAccess,
+        key: &str,
+        by: i64,
+        scope: Option<PoolScope>,
+    ) -> Result<(Option<Value>, i64, PoolScope), ResponseContent> {
+        let mut map = self.map.lock().unwrap();
+        let entry = map.get(key);
+        access.check(entry)?;
+        let scope = access.scope(entry, scope)?;
+        let previous = entry.map(|entry| entry.value.clone());
+        let current = match &previous {
+            Some(value) => value
+                .as_i64()
+                .ok_or_else(|| invalid(format!("value of {key} is not an integer")))?,
+            None => 0,
+        };
+        let value = current
+            .checked_add(by)
+            .ok_or_else(|| invalid(format!("value of {key} overflows")))?;
+
+        map.insert(
+            key.to_string(),
+            Entry {
+                value: Value::from(value),
+                scope,
+            },
+        );
+        Ok((previous, value, scope))
+    }
+
+    /// remove a key only if its current value is expected
+    /// returns if it is removed, the value it ends up with and its scope
+    pub fn remove_if(
+        &self,
+        access: &PoolAccess,
+        key: &str,
+        expected: &Value,
+    ) -> Result<(bool, Option<Value>, PoolScope), ResponseContent> {
+        let mut map = self.map.lock().unwrap();
+        access.check(map.get(key))?;
+        match map.get(key) {
+            Some(entry) if &entry.value == expected => {
+                let scope = entry.scope;
+                map.remove(key);
+                Ok((true, None, scope))
+            }
+            Some(entry) => Ok((false, Some(entry.value.clone()), entry.scope)),
+            None => Ok((false, None, PoolScope::default())),
+        }
+    }
+
+    /// all keys starting with prefix the component can see, sorted
+    pub fn keys(&self, access: &PoolAccess, prefix: &str) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .map
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(key, entry)| key.starts_with(prefix) && access.sees(&entry.scope))
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+/// a request that cannot be done to the pool
+fn invalid(message: String) -> ResponseContent {
+    ResponseContent::Error {
+        content: ResponseError::InvalidRequest,
+        message,
+    }
+}
+
+/// error if a pointer is not empty or a json pointer
+fn check_pointer(pointer: &str) -> Result<(), ResponseContent> {
+    match pointer {
+        "" => Ok(()),
+        pointer => split_pointer(pointer).map(|_| ()).map_err(invalid),
+    }
+}
+
+/// split a json pointer into the pointer to its parent and its last token
+fn split_pointer(pointer: &str) -> Result<(&str, String), String> {
+    match pointer.rfind('/') {
+        Some(index) if pointer.starts_with('/') => Ok((
+            &pointer[..index],
+            pointer[index + 1..].replace("~1", "/").replace("~0", "~"),
+        )),
+        _ => Err(format!("{pointer} is not a json pointer")),
+    }
+}
+
+/// index into an array from a pointer token
+fn array_index(token: &str) -> Result<usize, String> {
+    token
+        .parse()
+        .map_err(|_| format!("{token} is not an array index"))
+}
+
+/// set the value at a pointer, returns the value it replaced
+/// `-` or the length of an array appends to it
+fn set_at(root: &mut Value, pointer: &str, value: Value) -> Result<Option<Value>, String> {
+    let (parent, token) = split_pointer(pointer)?;
+    match root.pointer_mut(parent) {
+        Some(Value::Object(map)) => Ok(map.insert(token, value)),
+        Some(Value::Array(items)) if token == "-" => {
+            items.push(value);
+            Ok(None)
+        }
+        Some(Value::Array(items)) => {
+            let index = array_index(&token)?;
+            match index.cmp(&items.len()) {
+                Ordering::Less => Ok(Some(mem::replace(&mut items[index], value))),
+                Ordering::Equal => {
+                    items.push(value);
+                    Ok(None)
+                }
+                Ordering::Greater => Err(format!("{pointer} is out of bounds")),
+            }
+        }
+        Some(_) => Err(format!("{parent} is not an object or array")),
+        None => Err(format!("{parent} does not exist")),
+    }
+}
+
+/// remove the value at a pointer, returns the value removed
+fn remove_at(root: &mut Value, pointer: &str) -> Result<Option<Value>, String> {
+    let (parent, token) = split_pointer(pointer)?;
+    match root.pointer_mut(parent) {
+        Some(Value::Object(map)) => Ok(map.remove(&token)),
+        Some(Value::Array(items)) => {
+            let index = array_index(&token)?;
+            Ok((index < items.len()).then(|| items.remove(index)))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{Pool, PoolAccess};
+    use crate::structs::{Discriminator, PoolScope, ResponseContent, ResponseError};
+
+    /// access by ccanvas itself, which sees everything
+    fn ccanvas(owner: &Discriminator) -> PoolAccess<'_> {
+        PoolAccess {
+            owner,
+            space: owner,
+            component: None,
+        }
+    }
+
+    /// value at a pointer in a key, ignoring its scope
+    fn get(pool: &Pool, access: &PoolAccess, key: &str, pointer: &str) -> Option<Value> {
+        pool.get(access, key, pointer)
+            .unwrap()
+            .map(|(value, _)| value)
+    }
+
+    fn denied<T>(result: Result<T, ResponseContent>) -> bool {
+        matches!(
+            result,
+            Err(ResponseContent::Error {
+                content: ResponseError::PermissionDenied,
+                ..
+            })
+        )
+    }
+
+    #[test]
+    fn set_get_remove() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        let edit = pool.set(&access, "a", "", json!(1), None).unwrap();
+        assert_eq!(
+            (edit.replaced, edit.old, edit.new),
+            (None, None, Some(json!(1)))
+        );
+        let edit = pool.set(&access, "a", "", json!(2), None).unwrap();
+        assert_eq!(edit.replaced, Some(json!(1)));
+        assert_eq!(get(&pool, &access, "a", ""), Some(json!(2)));
+
+        let edit = pool.remove(&access, "a", "").unwrap();
+        assert_eq!((edit.replaced, edit.new), (Some(json!(2)), None));
+        assert_eq!(get(&pool, &access, "a", ""), None);
+    }
+
+    #[test]
+    fn keys_sorted_by_prefix() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        for key in ["b/2", "a", "b/1", "c/b"] {
+            pool.set(&access, key, "", Value::Null, None).unwrap();
+        }
+        assert_eq!(pool.keys(&access, "b/"), vec!["b/1", "b/2"]);
+        assert_eq!(pool.keys(&access, "").len(), 4);
+    }
+
+    #[test]
+    fn get_at_pointer() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        pool.set(&access, "a", "", json!({"b": [1, {"c~/d": 2}]}), None)
+            .unwrap();
+        assert_eq!(get(&pool, &access, "a", "/b/0"), Some(json!(1)));
+        assert_eq!(get(&pool, &access, "a", "/b/1/c~0~1d"), Some(json!(2)));
+        assert_eq!(get(&pool, &access, "a", "/b/2"), None);
+        assert_eq!(get(&pool, &access, "a", "/x"), None);
+    }
+
+    #[test]
+    fn set_at_pointer() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        assert!(pool.set(&access, "a", "/b", json!(1), None).is_err());
+
+        pool.set(&access, "a", "", json!({"b": [1]}), None).unwrap();
+        let edit = pool.set(&access, "a", "/c", json!(true), None).unwrap();
+        assert_eq!(edit.replaced, None);
+        assert_eq!(edit.old, Some(json!({"b": [1]})));
+        assert_eq!(edit.new, Some(json!({"b": [1], "c": true})));
+
+        // arrays can be replaced into or appended to, but not past the end
+        let edit = pool.set(&access, "a", "/b/0", json!(2), None).unwrap();
+        assert_eq!(edit.replaced, Some(json!(1)));
+        pool.set(&access, "a", "/b/-", json!(3), None).unwrap();
+        pool.set(&access, "a", "/b/2", json!(4), None).unwrap();
+        for pointer in ["/b/9", "/b/x", "/c/d", "/x/y"] {
+            assert!(pool.set(&access, "a", pointer, json!(5), None).is_err());
+        }
+        assert_eq!(get(&pool, &access, "a", "/b"), Some(json!([2, 3, 4])));
+    }
+
+    #[test]
+    fn remove_at_pointer() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        pool.set(&access, "a", "", json!({"b": [1, 2], "c": 3}), None)
+            .unwrap();
+        let edit = pool.remove(&access, "a", "/b/0").unwrap();
+        assert_eq!(edit.replaced, Some(json!(1)));
+        assert_eq!(edit.new, Some(json!({"b": [2], "c": 3})));
+
+        let removed = |key, pointer| pool.remove(&access, key, pointer).unwrap().replaced;
+        assert_eq!(removed("a", "/b/5"), None);
+        assert_eq!(removed("a", "/c"), Some(json!(3)));
+        assert_eq!(removed("x", "/c"), None);
+        assert_eq!(get(&pool, &access, "a", ""), Some(json!({"b": [2]})));
+    }
+
+    #[test]
+    fn malformed_pointers_are_invalid() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        let invalid = |result: Result<(), ResponseContent>| {
+            matches!(
+                result,
+                Err(ResponseContent::Error {
+                    content: ResponseError::InvalidRequest,
+                    ..
+                })
+            )
+        };
+        // missing keys are checked too, not just reported as not found
+        for key in ["a", "x"] {
+            pool.set(&access, "a", "", json!({"b": 1}), None).unwrap();
+            assert!(invalid(pool.get(&access, key, "b").map(|_| ())));
+            assert!(invalid(pool.remove(&access, key, "b").map(|_| ())));
+            assert!(invalid(
+                pool.set(&access, key, "b", json!(2), None).map(|_| ())
+            ));
+        }
+        assert_eq!(get(&pool, &access, "a", ""), Some(json!({"b": 1})));
+    }
+
+    #[test]
+    fn compare_and_set() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        let cas = |expected: Option<Value>, value| {
+            let (set, current, _) = pool
+                .compare_and_set(&access, "a", expected.as_ref(), value, None)
+                .unwrap();
+            (set, current)
+        };
+        assert_eq!(cas(Some(json!(1)), json!(2)), (false, None));
+        assert_eq!(cas(None, json!(1)), (true, Some(json!(1))));
+        assert_eq!(cas(None, json!(2)), (false, Some(json!(1))));
+        assert_eq!(
+            cas(Some(json!(1)), json!({"b": 2})),
+            (true, Some(json!({"b": 2})))
+        );
+        assert_eq!(get(&pool, &access, "a", ""), Some(json!({"b": 2})));
+    }
+
+    #[test]
+    fn increment() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        let increment = |key, by| {
+            pool.increment(&access, key, by, None)
+                .map(|(previous, value, _)| (previous, value))
+        };
+        assert_eq!(increment("n", 2), Ok((None, 2)));
+        assert_eq!(increment("n", -5), Ok((Some(json!(2)), -3)));
+        assert_eq!(get(&pool, &access, "n", ""), Some(json!(-3)));
+
+        pool.set(&access, "s", "", json!("1"), None).unwrap();
+        assert!(increment("s", 1).is_err());
+        pool.set(&access, "max", "", json!(i64::MAX), None).unwrap();
+        assert!(increment("max", 1).is_err());
+        assert_eq!(get(&pool, &access, "max", ""), Some(json!(i64::MAX)));
+    }
+
+    #[test]
+    fn remove_if() {
+        let (pool, master) = (Pool::default(), Discriminator::master());
+        let access = ccanvas(&master);
+        let remove_if = |expected| {
+            let (removed, current, _) = pool.remove_if(&access, "a", &expected).unwrap();
+            (removed, current)
+        };
+        assert_eq!(remove_if(json!(1)), (false, None));
+        pool.set(&access, "a", "", json!(1), None).unwrap();
+        assert_eq!(remove_if(json!(2)), (false, Some(json!(1))));
+        assert_eq!(remove_if(json!(1)), (true, None));
+        assert_eq!(get(&pool, &access, "a", ""), None);
+    }
+
+    #[test]
+    fn hidden_keys_are_denied() {
+        let pool = Pool::default();
+        let (owner, space, other) = (
+            Discriminator(vec![1, 2]),
+            Discriminator::master(),
+            Discriminator(vec![1, 3]),
+        );
+        let as_owner = PoolAccess {
+            owner: &owner,
+            space: &space,
+            component: Some(&owner),
+        };
+        let as_other = PoolAccess {
+            component: Some(&other),
+            ..as_owner
+        };
+        pool.set(&as_owner, "private", "", json!(1), Some(PoolScope::Private))
+            .unwrap();
+        pool.set(&as_owner, "shared", "", json!(2), Some(PoolScope::Space))
+            .unwrap();
+
+        assert!(denied(pool.get(&as_other, "private", "")));
+        assert!(denied(pool.set(&as_other, "private", "", json!(3), None)));
+        assert!(denied(pool.remove(&as_other, "private", "")));
+        assert!(denied(pool.increment(&as_other, "private", 1, None)));
+        assert_eq!(pool.keys(&as_other, ""), vec!["shared"]);
+        assert_eq!(pool.keys(&as_owner, ""), vec!["private", "shared"]);
+        assert_eq!(
+            pool.get(&as_other, "shared", "").unwrap(),
+            Some((json!(2), PoolScope::Space))
+        );
+
+        // only the owner can change the scope of a key
+        assert!(denied(pool.set(
+            &as_other,
+            "shared",
+            "",
+            json!(3),
+            Some(PoolScope::Global)
+        )));
+        pool.set(&as_other, "shared", "", json!(3), None).unwrap();
+        let edit = pool
+            .set(&as_owner, "shared", "", json!(4), Some(PoolScope::Private))
+            .unwrap();
+        assert_eq!(edit.scope, PoolScope::Private);
+        assert_eq!(pool.keys(&as_other, ""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn only_the_owner_sets_scopes() {
+        let pool = Pool::default();
+        let (owner, space, other) = (
+            Discriminator(vec![1, 2]),
+            Discriminator::master(),
+            Discriminator(vec![1, 3]),
+        );
+        let as_owner = PoolAccess {
+            owner: &owner,
+            space: &space,
+            component: Some(&owner),
+        };
+        let as_other = PoolAccess {
+            component: Some(&other),
+            ..as_owner
+        };
+
+        // new keys are as tight as they can be while still seen by whoever set them
+        let scope = |access, key| pool.set(access, key, "", json!(1), None).unwrap().scope;
+        assert_eq!(scope(&as_owner, "mine"), PoolScope::Private);
+        assert_eq!(scope(&as_other, "theirs"), PoolScope::Space);
+        assert_eq!(
+            pool.increment(&as_other, "count", 1, None).unwrap().2,
+            PoolScope::Space
+        );
+
+        // not even for new keys, or to the scope a key already has
+        for key in ["new", "theirs"] {
+            for scope in PoolScope::ALL {
+                assert!(denied(pool.set(&as_other, key, "", json!(2), Some(scope))));
+                assert!(denied(pool.increment(&as_other, key, 1, Some(scope))));
+            }
+        }
+        assert!(denied(pool.compare_and_set(
+            &as_other,
+            "new",
+            None,
+            json!(2),
+            Some(PoolScope::Private)
+        )));
+        assert_eq!(pool.keys(&as_other, ""), vec!["count", "theirs"]);
+    }
 }
@@ -6,7 +6,6 @@ use std::{
 use tokio::fs;
 
 use crate::structs::Discriminator;
-use crate::values::ROOT;
 
 /// wrapper struct for storage of a single component
 #[derive(Clone)]
@@ -16,9 +15,9 @@ pub struct Storage {
 }
 
 impl Storage {
-    /// creates a new struct and the corresponding directory
-    pub async fn new(discrim: &Discriminator) -> Self {
-        let path = ROOT.get().unwrap().join(PathBuf::from_iter(
+    /// creates a new struct and the corresponding directory under root
+    pub async fn new(root: &Path, discrim: &Discriminator) -> Self {
+        let path = root.join(PathBuf::from_iter(
             discrim.as_vec().iter().map(u32::to_string),
         ));
 
@@ -1,7 +1,11 @@
-use crate::structs::{Discriminator, Packet, Request, Response, Subscription};
+use crate::structs::{
+    Discriminator, Packet, PoolScope, PoolTarget, Request, RequestContent, Response, Subscription,
+};
 
 use super::{KeyEvent, MouseEvent};
 
+use serde_json::Value;
+
 use termion::event::Event as TermionEvent;
 
 /// a basic, generic unit of event
@@ -23,6 +27,17 @@ pub enum Event {
         target: Discriminator,
         content: String,
     },
+    /// a key in the pool of a component is set or removed
+    /// only components that can see the key are told about it
+    PoolChange {
+        pool: Discriminator,
+        /// space the pool belongs to
+        space: Discriminator,
+        key: String,
+        scope: PoolScope,
+        old: Option<Value>,
+        new: Option<Value>,
+    },
 }
 
 impl TryFrom<TermionEvent> for Event {
@@ -54,6 +69,21 @@ impl Clone for Event {
                 target: target.clone(),
                 content: content.clone(),
             },
+            Self::PoolChange {
+                pool,
+                space,
+                key,
+                scope,
+                old,
+                new,
+            } => Self::PoolChange {
+                pool: pool.clone(),
+                space: space.clone(),
+                key: key.clone(),
+                scope: *scope,
+                old: old.clone(),
+                new: new.clone(),
+            },
             Self::RequestPacket(_) => panic!("bad clone"),
         }
     }
@@ -85,10 +115,48 @@ impl Event {
             Self::ScreenResize(..) => vec![Subscription::ScreenResize],
             Self::Focus { .. } => vec![Subscription::Focused],
             Self::Unfocus => vec![Subscription::Unfocused],
+            Self::PoolChange { pool, key, .. } => {
+                let pool = PoolTarget::Component {
+                    discrim: pool.clone(),
+                };
+                // the key itself, and every prefix of it
+                let prefixes = key
+                    .char_indices()
+                    .map(|(end, _)| end)
+                    .chain([key.len()])
+                    .map(|end| Subscription::PoolChange {
+                        pool: pool.clone(),
+                        key: key[..end].to_string(),
+                        prefix: true,
+                    });
+                [Subscription::PoolChange {
+                    pool: pool.clone(),
+                    key: key.clone(),
+                    prefix: false,
+                }]
+                .into_iter()
+                .chain(prefixes)
+                .collect()
+            }
             Self::RequestPacket(_) => Vec::new(),
         }
     }
 
+    /// if the screen should be composited again after the event is handled
+    pub fn redraws(&self) -> bool {
+        match self {
+            Self::ScreenResize(..) => true,
+            Self::RequestPacket(packet) => match packet.get().content() {
+                RequestContent::Render { content, flush, .. } => *flush || content.flushes(),
+                RequestContent::FocusAt
+                | RequestContent::Drop { .. }
+                | RequestContent::SetLayout { .. } => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
     pub fn from_packet(packet: Packet<Request, Response>) -> Self {
         Self::RequestPacket(packet)
     }
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use termion::event::Key as TermionKey;
 
 /// a single keyboard event
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash, JsonSchema)]
 pub struct KeyEvent {
     /// the keycode represented by the characetr
     pub code: KeyCode,
@@ -33,7 +34,7 @@ impl KeyEvent {
 }
 
 /// a unique key (non modifier keys)
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash, JsonSchema)]
 pub enum KeyCode {
     /// Backspace.
     #[serde(rename = "backspace")]
@@ -119,7 +120,7 @@ impl TryFrom<TermionKey> for KeyCode {
 ///
 /// no shift, as it is not a real modifier
 /// check if shift might be pressed yourself using is_upper_case
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash, JsonSchema)]
 pub enum KeyModifier {
     #[serde(rename = "alt")]
     Alt,
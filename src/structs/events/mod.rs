@@ -6,5 +6,3 @@ pub use keyevent::*;
 
 mod mouseevent;
 pub use mouseevent::*;
-
-mod listeners;
@@ -1,10 +1,14 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use termion::event;
 
+use crate::structs::Rect;
+
 /// a single mouse event
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub struct MouseEvent {
     /// where the mouse event is
+    /// relative to the region of the reciever
     x: u32,
     y: u32,
     /// what kind of event it is
@@ -33,8 +37,29 @@ impl From<event::MouseEvent> for MouseEvent {
     }
 }
 
+impl MouseEvent {
+    /// column of the mouse event
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// row of the mouse event
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// same event with coordinates relative to the top left corner of rect
+    pub fn relative_to(&self, rect: &Rect) -> Self {
+        Self {
+            x: self.x.saturating_sub(rect.x),
+            y: self.y.saturating_sub(rect.y),
+            ..*self
+        }
+    }
+}
+
 /// what kind of mouse event it is
-#[derive(Hash, PartialEq, Eq, Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Hash, PartialEq, Eq, Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
 pub enum MouseType {
     #[serde(rename = "left")]
     /// The left mouse button.
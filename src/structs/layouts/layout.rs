@@ -0,0 +1,237 @@
+use schemars::JsonSchema;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::structs::Discriminator;
+
+use super::Rect;
+
+/// how a space arranges its children
+/// children not mentioned in the layout take up the whole space
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug, JsonSchema)]
+#[serde(tag = "type")]
+pub enum Layout {
+    /// every child takes up the whole space, only the focused space is shown
+    /// like tabs stacked on top of each other
+    #[serde(rename = "stacked")]
+    #[default]
+    Stacked,
+    /// children placed left to right
+    #[serde(rename = "horizontal")]
+    Horizontal { children: Vec<LayoutItem> },
+    /// children placed top to bottom
+    #[serde(rename = "vertical")]
+    Vertical { children: Vec<LayoutItem> },
+    /// children placed at rects relative to the space
+    /// later children are drawn on top
+    #[serde(rename = "floating")]
+    Floating { children: Vec<FloatingItem> },
+}
+
+/// a child in a split layout
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+pub struct LayoutItem {
+    pub discrim: Discriminator,
+    pub size: Constraint,
+}
+
+/// a child in a floating layout
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+pub struct FloatingItem {
+    pub discrim: Discriminator,
+    pub rect: Rect,
+}
+
+/// size of a child in a split layout
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(tag = "type")]
+pub enum Constraint {
+    /// exact number of columns or rows
+    #[serde(rename = "fixed")]
+    Fixed { size: u32 },
+    /// share of whatever is left after fixed children are placed
+    #[serde(rename = "ratio")]
+    Ratio { ratio: u32 },
+}
+
+impl Layout {
+    /// if all children are shown at once, instead of only the focused one
+    pub fn is_tiled(&self) -> bool {
+        self != &Self::Stacked
+    }
+
+    /// children in the layout, in drawing order
+    pub fn children(&self) -> Vec<&Discriminator> {
+        match self {
+            Self::Stacked => Vec::new(),
+            Self::Horizontal { children } | Self::Vertical { children } => {
+                children.iter().map(|item| &item.discrim).collect()
+            }
+            Self::Floating { children } => children.iter().map(|item| &item.discrim).collect(),
+        }
+    }
+
+    /// children in the layout containing the point, topmost first
+    pub fn hit_test<'a>(
+        &'a self,
+        regions: &HashMap<Discriminator, Rect>,
+        x: u32,
+        y: u32,
+    ) -> Vec<&'a Discriminator> {
+        self.children()
+            .into_iter()
+            .rev()
+            .filter(|discrim| {
+                regions
+                    .get(*discrim)
+                    .is_some_and(|region| region.contains(x, y))
+            })
+            .collect()
+    }
+
+    /// compute rects of children in the layout
+    pub fn compute(&self, region: Rect) -> HashMap<Discriminator, Rect> {
+        match self {
+            Self::Stacked => HashMap::new(),
+            Self::Horizontal { children } => split(children, region.width)
+                .into_iter()
+                .map(|(discrim, offset, size)| {
+                    (
+                        discrim,
+                        Rect::new(region.x + offset, region.y, size, region.height),
+                    )
+                })
+                .collect(),
+            Self::Vertical { children } => split(children, region.height)
+                .into_iter()
+                .map(|(discrim, offset, size)| {
+                    (
+                        discrim,
+                        Rect::new(region.x, region.y + offset, region.width, size),
+                    )
+                })
+                .collect(),
+            Self::Floating { children } => children
+                .iter()
+                .map(|item| (item.discrim.clone(), region.inner(&item.rect)))
+                .collect(),
+        }
+    }
+}
+
+/// split length between children, returns (discrim, offset, size)
+fn split(children: &[LayoutItem], length: u32) -> Vec<(Discriminator, u32, u32)> {
+    let fixed = children
+        .iter()
+        .map(|item| match item.size {
+            Constraint::Fixed { size } => size,
+            Constraint::Ratio { .. } => 0,
+        })
+        .fold(0_u32, u32::saturating_add);
+    let ratios = children
+        .iter()
+        .map(|item| match item.size {
+            Constraint::Fixed { .. } => 0,
+            Constraint::Ratio { ratio } => ratio,
+        })
+        .fold(0_u32, u32::saturating_add);
+    let remaining = length.saturating_sub(fixed) as u64;
+
+    let mut offset = 0;
+    // sum of ratios placed so far
+    // sizes are taken as differences of running totals so nothing is lost to rounding
+    let mut placed = 0_u64;
+    children
+        .iter()
+        .map(|item| {
+            let size = match item.size {
+                Constraint::Fixed { size } => size,
+                Constraint::Ratio { ratio } => {
+                    let before = remaining * placed / ratios.max(1) as u64;
+                    placed += ratio as u64;
+                    (remaining * placed / ratios.max(1) as u64 - before) as u32
+                }
+            }
+            .min(length - offset);
+            let out = (item.discrim.clone(), offset, size);
+            offset += size;
+            out
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split, Constraint, Layout, LayoutItem, Rect};
+    use crate::structs::Discriminator;
+
+    fn items(sizes: &[Constraint]) -> Vec<LayoutItem> {
+        let master = Discriminator::master();
+        sizes
+            .iter()
+            .zip(1..)
+            .map(|(size, id)| LayoutItem {
+                discrim: master.new_child(id),
+                size: *size,
+            })
+            .collect()
+    }
+
+    /// (offset, size) of each child
+    fn sizes(children: &[LayoutItem], length: u32) -> Vec<(u32, u32)> {
+        split(children, length)
+            .into_iter()
+            .map(|(_, offset, size)| (offset, size))
+            .collect()
+    }
+
+    #[test]
+    fn ratios_share_length() {
+        let children = items(&[
+            Constraint::Ratio { ratio: 1 },
+            Constraint::Ratio { ratio: 3 },
+        ]);
+        assert_eq!(sizes(&children, 8), vec![(0, 2), (2, 6)]);
+    }
+
+    #[test]
+    fn ratios_lose_nothing_to_rounding() {
+        let children = items(&[Constraint::Ratio { ratio: 1 }; 3]);
+        assert_eq!(sizes(&children, 10), vec![(0, 3), (3, 3), (6, 4)]);
+    }
+
+    #[test]
+    fn fixed_placed_before_ratios() {
+        let children = items(&[
+            Constraint::Ratio { ratio: 1 },
+            Constraint::Fixed { size: 4 },
+            Constraint::Ratio { ratio: 1 },
+        ]);
+        assert_eq!(sizes(&children, 10), vec![(0, 3), (3, 4), (7, 3)]);
+    }
+
+    #[test]
+    fn fixed_cut_off_at_length() {
+        let children = items(&[
+            Constraint::Fixed { size: 6 },
+            Constraint::Fixed { size: 6 },
+            Constraint::Ratio { ratio: 1 },
+        ]);
+        assert_eq!(sizes(&children, 10), vec![(0, 6), (6, 4), (10, 0)]);
+    }
+
+    #[test]
+    fn vertical_offsets_from_region() {
+        let children = items(&[
+            Constraint::Fixed { size: 1 },
+            Constraint::Ratio { ratio: 1 },
+        ]);
+        let rects = Layout::Vertical {
+            children: children.clone(),
+        }
+        .compute(Rect::new(2, 3, 5, 4));
+        assert_eq!(rects[&children[0].discrim], Rect::new(2, 3, 5, 1));
+        assert_eq!(rects[&children[1].discrim], Rect::new(2, 4, 5, 3));
+    }
+}
@@ -0,0 +1,5 @@
+mod layout;
+pub use layout::*;
+
+mod rect;
+pub use rect::*;
@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// a rectangular region on the screen
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug, Hash, JsonSchema)]
+pub struct Rect {
+    /// column of the top left corner
+    pub x: u32,
+    /// row of the top left corner
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// construct new self
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// one column right of the rightmost column
+    pub fn right(&self) -> u32 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// one row below the bottom row
+    pub fn bottom(&self) -> u32 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// check if a point is in self
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        (self.x..self.right()).contains(&x) && (self.y..self.bottom()).contains(&y)
+    }
+
+    /// the overlapping part of two rects, empty if they do not overlap
+    pub fn intersect(&self, other: &Self) -> Self {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Self {
+            x,
+            y,
+            width: self.right().min(other.right()).saturating_sub(x),
+            height: self.bottom().min(other.bottom()).saturating_sub(y),
+        }
+    }
+
+    /// a rect relative to self, clipped to self
+    pub fn inner(&self, relative: &Self) -> Self {
+        Self {
+            x: self.x.saturating_add(relative.x),
+            y: self.y.saturating_add(relative.y),
+            ..*relative
+        }
+        .intersect(self)
+    }
+}
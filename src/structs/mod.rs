@@ -15,3 +15,15 @@ pub use component::*;
 
 mod data;
 pub use data::*;
+
+mod render;
+pub use render::*;
+
+mod layouts;
+pub use layouts::*;
+
+mod protocol;
+pub use protocol::*;
+
+mod context;
+pub use context::*;
@@ -0,0 +1,315 @@
+use schemars::JsonSchema;
+use std::io::{self, BufRead, Read};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// how requests and responses are written on a persistent connection
+///
+/// json is newline delimited, binary encodings are prefixed
+/// with their length as a big endian u32
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default, JsonSchema)]
+pub enum Encoding {
+    #[serde(rename = "json")]
+    #[default]
+    Json,
+    #[serde(rename = "msgpack")]
+    MessagePack,
+    #[serde(rename = "cbor")]
+    Cbor,
+}
+
+impl Encoding {
+    /// if ccanvas is built with support for the encoding
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Self::Json => true,
+            Self::MessagePack => cfg!(feature = "msgpack"),
+            Self::Cbor => cfg!(feature = "cbor"),
+        }
+    }
+
+    /// the encoding to use when a component asks for one
+    /// falls back to json if it is not supported
+    pub fn negotiate(requested: Self) -> Self {
+        if requested.is_supported() {
+            requested
+        } else {
+            Self::Json
+        }
+    }
+
+    /// serialise a value into a single frame
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        if self == &Self::Json {
+            let mut frame = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+            frame.push(b'\n');
+            return Ok(frame);
+        }
+
+        let payload: Vec<u8> = match self {
+            // tagged enums need field names
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => {
+                let mut payload = Vec::new();
+                ciborium::into_writer(value, &mut payload)
+                    .map(|_| payload)
+                    .map_err(|e| e.to_string())
+            }
+            _ => Err(format!("{self:?} is not supported")),
+        }?;
+
+        let len = u32::try_from(payload.len()).map_err(|e| e.to_string())?;
+        Ok(len.to_be_bytes().into_iter().chain(payload).collect())
+    }
+
+    /// deserialise the content of a frame
+    pub fn decode<T: DeserializeOwned>(&self, frame: &[u8]) -> Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_slice(frame).map_err(|e| e.to_string()),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::from_slice(frame).map_err(|e| e.to_string()),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => ciborium::from_reader(frame).map_err(|e| e.to_string()),
+            #[allow(unreachable_patterns)]
+            _ => Err(format!("{self:?} is not supported")),
+        }
+    }
+
+    /// read the content of the next frame, None if the reader is closed
+    /// frames larger than max_size are an error, and nothing is allocated for them
+    pub fn read_frame(
+        &self,
+        reader: &mut impl BufRead,
+        max_size: u32,
+    ) -> io::Result<Option<Vec<u8>>> {
+        if self == &Self::Json {
+            let mut line = Vec::new();
+            // one more byte than allowed, for the newline
+            let limit = u64::from(max_size) + 1;
+            if reader.by_ref().take(limit).read_until(b'\n', &mut line)? == 0 {
+                return Ok(None);
+            }
+            if line.last() == Some(&b'\n') {
+                line.pop();
+            } else if line.len() as u64 == limit {
+                return Err(too_large(max_size));
+            }
+            return Ok(Some(line));
+        }
+
+        let mut len = [0; 4];
+        match reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        let len = u32::from_be_bytes(len);
+        if len > max_size {
+            return Err(too_large(max_size));
+        }
+
+        let mut frame = vec![0; len as usize];
+        reader.read_exact(&mut frame)?;
+        Ok(Some(frame))
+    }
+
+    /// read_frame, for readers on the async runtime
+    pub async fn read_frame_async(
+        &self,
+        reader: &mut (impl AsyncBufRead + Unpin),
+        max_size: u32,
+    ) -> io::Result<Option<Vec<u8>>> {
+        if self == &Self::Json {
+            let mut line = Vec::new();
+            // one more byte than allowed, for the newline
+            let limit = u64::from(max_size) + 1;
+            if reader.take(limit).read_until(b'\n', &mut line).await? == 0 {
+                return Ok(None);
+            }
+            if line.last() == Some(&b'\n') {
+                line.pop();
+            } else if line.len() as u64 == limit {
+                return Err(too_large(max_size));
+            }
+            return Ok(Some(line));
+        }
+
+        let mut len = [0; 4];
+        match reader.read_exact(&mut len).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        let len = u32::from_be_bytes(len);
+        if len > max_size {
+            return Err(too_large(max_size));
+        }
+
+        let mut frame = vec![0; len as usize];
+        reader.read_exact(&mut frame).await?;
+        Ok(Some(frame))
+    }
+
+    /// read everything until the reader is closed, for one-shot connections
+    /// which are always json and have nothing to delimit frames
+    pub async fn read_all(
+        reader: &mut (impl AsyncRead + Unpin),
+        max_size: u32,
+    ) -> io::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        let limit = u64::from(max_size) + 1;
+        if reader.take(limit).read_to_end(&mut content).await? as u64 == limit {
+            return Err(too_large(max_size));
+        }
+        Ok(content)
+    }
+}
+
+/// error for a frame larger than allowed
+fn too_large(max_size: u32) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("frame is larger than {max_size} bytes"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, ErrorKind};
+
+    use serde_json::{json, Value};
+
+    use super::Encoding;
+
+    /// write values as frames, then read them back in order
+    fn round_trip(encoding: Encoding, values: &[Value]) -> Vec<Value> {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| encoding.encode(value).unwrap())
+            .collect();
+        let mut reader = Cursor::new(bytes);
+        let mut out = Vec::new();
+        while let Some(frame) = encoding.read_frame(&mut reader, u32::MAX).unwrap() {
+            out.push(encoding.decode(&frame).unwrap());
+        }
+        out
+    }
+
+    fn values() -> Vec<Value> {
+        vec![
+            json!({"type": "hello", "version": 1, "encoding": "msgpack"}),
+            json!({"nested": {"list": [1, "two", null]}, "empty": ""}),
+        ]
+    }
+
+    #[test]
+    fn json_round_trip() {
+        assert_eq!(round_trip(Encoding::Json, &values()), values());
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_round_trip() {
+        assert_eq!(round_trip(Encoding::MessagePack, &values()), values());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn cbor_round_trip() {
+        assert_eq!(round_trip(Encoding::Cbor, &values()), values());
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn binary_frames_decode_tagged_requests() {
+        use crate::structs::RequestContent;
+
+        let frame = Encoding::MessagePack.encode(&values()[0]).unwrap();
+        let content: RequestContent = Encoding::MessagePack.decode(&frame[4..]).unwrap();
+        assert_eq!(
+            content,
+            RequestContent::Hello {
+                version: 1,
+                features: Vec::new(),
+                encoding: Encoding::MessagePack,
+            }
+        );
+    }
+
+    #[test]
+    fn truncated_frame_is_an_error() {
+        // length says 8 bytes, but only 1 follows
+        let frame = vec![0, 0, 0, 8, 1];
+        assert!(Encoding::Cbor
+            .read_frame(&mut Cursor::new(frame), u32::MAX)
+            .is_err());
+    }
+
+    /// error kind when reading a frame, None if it is read
+    fn read_error(encoding: Encoding, bytes: &[u8], max_size: u32) -> Option<ErrorKind> {
+        encoding
+            .read_frame(&mut Cursor::new(bytes), max_size)
+            .err()
+            .map(|e| e.kind())
+    }
+
+    #[test]
+    fn json_frames_up_to_limit() {
+        assert_eq!(read_error(Encoding::Json, b"1234\n", 4), None);
+        // the last line does not need a newline
+        assert_eq!(read_error(Encoding::Json, b"1234", 4), None);
+        assert_eq!(
+            read_error(Encoding::Json, b"12345\n", 4),
+            Some(ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn binary_frames_up_to_limit() {
+        assert_eq!(
+            read_error(Encoding::Cbor, &[0, 0, 0, 4, 1, 2, 3, 4], 4),
+            None
+        );
+        // rejected from the length alone, before anything is allocated
+        assert_eq!(
+            read_error(Encoding::MessagePack, &[255, 255, 255, 255], 4),
+            Some(ErrorKind::InvalidData)
+        );
+    }
+
+    #[tokio::test]
+    async fn async_frames_match_blocking_frames() {
+        for encoding in [Encoding::Json, Encoding::Cbor] {
+            let bytes = [0, 0, 0, 4, b'1', b'2', b'3', b'\n', 0, 0, 0, 9];
+            let mut blocking = Cursor::new(&bytes[..]);
+            let mut reader = &bytes[..];
+            loop {
+                let expected = encoding.read_frame(&mut blocking, 4).map_err(|e| e.kind());
+                let frame = encoding.read_frame_async(&mut reader, 4).await;
+                assert_eq!(frame.map_err(|e| e.kind()), expected);
+                if !matches!(expected, Ok(Some(_))) {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn one_shot_requests_up_to_limit() {
+        async fn read(mut bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+            Encoding::read_all(&mut bytes, 4).await
+        }
+        // one-shot requests may span several lines
+        assert_eq!(read(b"1\n23").await.unwrap(), b"1\n23");
+        assert_eq!(read(b"1234").await.unwrap(), b"1234");
+        assert_eq!(
+            read(b"12345").await.unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
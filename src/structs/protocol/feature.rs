@@ -0,0 +1,90 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// optional parts of the protocol a component may or may not understand
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, JsonSchema)]
+pub enum Feature {
+    /// newline delimited requests and responses over connection.sock
+    #[serde(rename = "connection")]
+    Connection,
+    /// newline delimited requests and responses over stdio
+    #[serde(rename = "stdio")]
+    Stdio,
+    /// text attributes in render requests
+    #[serde(rename = "styles")]
+    Styles,
+    /// regions and layouts of spaces
+    #[serde(rename = "layouts")]
+    Layouts,
+    /// get requests for component properties
+    #[serde(rename = "properties")]
+    Properties,
+    /// requests for reading and writing pools
+    #[serde(rename = "pool")]
+    Pool,
+    /// any feature this version of ccanvas does not know about
+    #[serde(other)]
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+impl Feature {
+    /// all features supported by this version of ccanvas
+    pub const SUPPORTED: &'static [Self] = &[
+        Self::Connection,
+        Self::Stdio,
+        Self::Styles,
+        Self::Layouts,
+        Self::Properties,
+        Self::Pool,
+    ];
+
+    /// features enabled in ccanvas and supported by the component
+    pub fn negotiate(enabled: &[Self], requested: &[Self]) -> Vec<Self> {
+        enabled
+            .iter()
+            .filter(|feature| requested.contains(feature))
+            .copied()
+            .collect()
+    }
+}
+
+impl fmt::Display for Feature {
+    /// the name used in the protocol
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Connection => "connection",
+            Self::Stdio => "stdio",
+            Self::Styles => "styles",
+            Self::Layouts => "layouts",
+            Self::Properties => "properties",
+            Self::Pool => "pool",
+            Self::Unknown => "unknown",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Feature;
+    use crate::structs::ResponseError;
+
+    #[test]
+    fn display_matches_protocol_names() {
+        for feature in Feature::SUPPORTED.iter().chain([&Feature::Unknown]) {
+            assert_eq!(
+                serde_json::to_string(feature).unwrap(),
+                format!("\"{feature}\"")
+            );
+        }
+        assert_eq!(
+            ResponseError::FeatureDisabled {
+                feature: Feature::Pool
+            }
+            .to_string(),
+            "feature pool is disabled"
+        );
+    }
+}
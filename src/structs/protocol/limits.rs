@@ -0,0 +1,52 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// protocol revision spoken by this version of ccanvas
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// oldest protocol revision still understood
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// if a component speaking a protocol revision can be served
+pub fn is_compatible(version: u32) -> bool {
+    (MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&version)
+}
+
+/// cargo features ccanvas is built with
+pub fn cargo_features() -> Vec<String> {
+    [
+        ("log", cfg!(feature = "log")),
+        ("msgpack", cfg!(feature = "msgpack")),
+        ("cbor", cfg!(feature = "cbor")),
+        ("client", cfg!(feature = "client")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(feature, _)| feature.to_string())
+    .collect()
+}
+
+/// hard limits a component should stay within
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+pub struct Limits {
+    /// largest request id a component may use
+    /// ids above are reserved for requests generated by ccanvas
+    pub max_request_id: u32,
+    /// widest a single cell can be, in columns
+    /// wider grapheme clusters still only take up this many
+    pub max_cell_width: u32,
+    /// largest frame that can be sent on a connection, in bytes
+    /// connections sending larger frames are closed
+    /// and larger one-shot requests are rejected
+    pub max_frame_size: u32,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_request_id: u32::MAX / 2,
+            max_cell_width: 2,
+            max_frame_size: 16 * 1024 * 1024,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+mod feature;
+pub use feature::*;
+
+mod limits;
+pub use limits::*;
+
+mod encoding;
+pub use encoding::*;
+
+mod schema;
+pub use schema::*;
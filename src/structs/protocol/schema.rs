@@ -0,0 +1,24 @@
+use schemars::gen::SchemaSettings;
+use serde_json::{json, Value};
+
+use crate::structs::{Request, Response};
+
+use super::PROTOCOL_VERSION;
+
+/// json schema of every message in the protocol
+///
+/// a message is either a request sent by a component,
+/// or a response sent by ccanvas
+pub fn schema() -> Value {
+    let mut gen = SchemaSettings::draft07().into_generator();
+    let request = gen.subschema_for::<Request>();
+    let response = gen.subschema_for::<Response>();
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "ccanvas protocol",
+        "version": PROTOCOL_VERSION,
+        "oneOf": [request, response],
+        "definitions": gen.definitions(),
+    })
+}
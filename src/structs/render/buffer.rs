@@ -0,0 +1,136 @@
+use crate::structs::Rect;
+
+use super::{Cell, View};
+
+/// an off-screen grid of cells
+///
+/// empty cells are transparent, and will show whatever is below them
+/// when overlayed onto another buffer
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Buffer {
+    width: u32,
+    height: u32,
+    /// row major cells, None if nothing has been drawn there
+    cells: Vec<Option<Cell>>,
+}
+
+impl Buffer {
+    /// create an empty buffer of size
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![None; (width * height) as usize],
+        }
+    }
+
+    /// returns (width, height)
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// change size of buffer, keeping content that is still in bounds
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if (width, height) == self.size() {
+            return;
+        }
+
+        let mut new = Self::new(width, height);
+        new.overlay(self, Rect::new(0, 0, width, height));
+        *self = new;
+    }
+
+    /// index of a cell in the vec, None if out of bounds
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| (y * self.width + x) as usize)
+    }
+
+    /// get cell at location
+    pub fn get(&self, x: u32, y: u32) -> Option<&Cell> {
+        self.index(x, y)
+            .and_then(|index| self.cells[index].as_ref())
+    }
+
+    /// set cell at location, does nothing if out of bounds
+    ///
+    /// wide cells take up the cell to the right as well,
+    /// halves of wide cells broken by this are removed
+    pub fn set(&mut self, x: u32, y: u32, mut cell: Cell) {
+        let index = match self.index(x, y) {
+            Some(index) => index,
+            None => return,
+        };
+
+        // a wide cell that does not fit on the row is replaced with a space
+        if cell.width() == 2 && x + 1 >= self.width {
+            cell.symbol = " ".to_string();
+        }
+
+        self.remove_halves(x, y);
+        if cell.width() == 2 {
+            self.remove_halves(x + 1, y);
+            self.cells[index + 1] = Some(cell.continuation());
+        }
+        self.cells[index] = Some(cell);
+    }
+
+    /// remove the other half of a wide cell at location, if there is one
+    fn remove_halves(&mut self, x: u32, y: u32) {
+        let index = match self.index(x, y) {
+            Some(index) => index,
+            None => return,
+        };
+
+        match &self.cells[index] {
+            Some(cell) if cell.is_continuation() && x > 0 => self.cells[index - 1] = None,
+            Some(cell) if cell.width() == 2 && x + 1 < self.width => self.cells[index + 1] = None,
+            _ => {}
+        }
+    }
+
+    /// set every cell in a rectangle, None makes them transparent
+    /// parts out of bounds are ignored
+    pub fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, cell: Option<Cell>) {
+        let rect = Rect::new(0, 0, self.width, self.height).inner(&Rect::new(x, y, width, height));
+        match cell {
+            Some(cell) => self
+                .view(rect)
+                .fill(0, 0, rect.width, rect.height, Some(cell)),
+            None => {
+                for y in rect.y..rect.bottom() {
+                    for x in rect.x..rect.right() {
+                        self.remove_halves(x, y);
+                        let index = (y * self.width + x) as usize;
+                        self.cells[index] = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// make every cell transparent
+    pub fn clear(&mut self) {
+        self.cells.iter_mut().for_each(|cell| *cell = None);
+    }
+
+    /// draw non empty cells of other within rect on top of self
+    pub fn overlay(&mut self, other: &Self, rect: Rect) {
+        let mut view = self.view(rect);
+        for y in rect.y..rect.bottom().min(other.height) {
+            for x in rect.x..rect.right().min(other.width) {
+                match other.get(x, y) {
+                    // continuations are set together with the wide cell
+                    Some(cell) if !cell.is_continuation() => {
+                        view.set(x - rect.x, y - rect.y, cell.clone())
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// a view of the part of self in rect
+    pub fn view(&mut self, rect: Rect) -> View<'_> {
+        View::new(self, rect)
+    }
+}
@@ -0,0 +1,96 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::structs::{Colour, Limits};
+
+use super::Style;
+
+/// a single character on the screen
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cell {
+    /// the grapheme cluster displayed
+    /// empty if the cell is covered by a wide cell on its left
+    pub symbol: String,
+    /// foreground colour
+    pub fg: Colour,
+    /// background colour
+    pub bg: Colour,
+    /// text attributes
+    pub style: Style,
+}
+
+impl Cell {
+    /// a cell with default colours
+    pub fn new(symbol: &str) -> Self {
+        Self::new_styled(symbol, Colour::Reset, Colour::Reset, Style::default())
+    }
+
+    /// a cell with fg and bg colours and text attributes
+    /// only the first grapheme cluster of symbol is kept
+    ///
+    /// clusters that take up no columns on their own (such as lone combining marks)
+    /// or contain control characters are replaced with a space
+    /// as the terminal would not draw them where the cell is
+    pub fn new_styled(symbol: &str, fg: Colour, bg: Colour, style: Style) -> Self {
+        let symbol = match symbol.graphemes(true).next() {
+            Some(symbol) if symbol.width() > 0 && !symbol.chars().any(char::is_control) => symbol,
+            _ => " ",
+        };
+
+        Self {
+            symbol: symbol.to_string(),
+            fg,
+            bg,
+            style,
+        }
+    }
+
+    /// the right half of a wide cell, with the same colours
+    pub fn continuation(&self) -> Self {
+        Self {
+            symbol: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// if the cell is the right half of a wide cell
+    pub fn is_continuation(&self) -> bool {
+        self.symbol.is_empty()
+    }
+
+    /// number of columns the cell takes up on screen
+    /// no wider than the max cell width in the limits
+    pub fn width(&self) -> u32 {
+        if self.is_continuation() {
+            0
+        } else {
+            (self.symbol.width() as u32).clamp(1, Limits::default().max_cell_width)
+        }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::new(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cell;
+    use crate::structs::Limits;
+
+    #[test]
+    fn width_within_limits() {
+        assert_eq!(Cell::new("a").width(), 1);
+        assert_eq!(Cell::new("世").width(), 2);
+        assert_eq!(Cell::new("世").continuation().width(), 0);
+
+        // symbol can be set to something wider than a cell can be
+        let cell = Cell {
+            symbol: "世界".to_string(),
+            ..Cell::default()
+        };
+        assert_eq!(cell.width(), Limits::default().max_cell_width);
+    }
+}
@@ -0,0 +1,185 @@
+use std::{fmt::Write as _, io::Write};
+
+use termion::{color, cursor, style};
+
+use crate::structs::Colour;
+
+use super::{Buffer, Cell, Style};
+
+/// pushes composited frames to the terminal
+/// only writing what changed since the last frame
+#[derive(Default)]
+pub struct Compositor {
+    /// what is currently on the screen
+    front: Buffer,
+}
+
+impl Compositor {
+    /// write cells that changed since the last frame to screen and flush
+    pub fn push(&mut self, frame: &Buffer, screen: &mut impl Write) {
+        let mut out = String::new();
+
+        // leftovers from a bigger screen should not stay around
+        if frame.size() != self.front.size() {
+            let (width, height) = frame.size();
+            write!(out, "{}", termion::clear::All).unwrap();
+            self.front = Buffer::new(width, height);
+        }
+
+        let (width, height) = frame.size();
+        let blank = Cell::default();
+
+        // the last frame always ends with colours and attributes reset
+        let mut fg = Colour::Reset;
+        let mut bg = Colour::Reset;
+        let mut attrs = Style::default();
+        // where the next character will be written without a goto
+        let mut next = None;
+
+        for y in 0..height {
+            for x in 0..width {
+                let cell = frame.get(x, y).unwrap_or(&blank);
+                if cell == self.front.get(x, y).unwrap_or(&blank) {
+                    continue;
+                }
+
+                // the wide cell on the left has already covered it
+                if cell.is_continuation() {
+                    self.front.set(x, y, cell.clone());
+                    continue;
+                }
+
+                // consecutive changed cells share one goto
+                if next != Some((x, y)) {
+                    write!(out, "{}", cursor::Goto(x as u16 + 1, y as u16 + 1)).unwrap();
+                }
+                if cell.style != attrs {
+                    // attributes cannot be reliably turned off one by one
+                    // so reset everything, colours included
+                    if !attrs.is_empty() {
+                        write!(out, "{}", style::Reset).unwrap();
+                        fg = Colour::Reset;
+                        bg = Colour::Reset;
+                    }
+                    write!(out, "{}", cell.style).unwrap();
+                    attrs = cell.style;
+                }
+                if cell.fg != fg {
+                    write!(out, "{}", color::Fg(cell.fg)).unwrap();
+                    fg = cell.fg;
+                }
+                if cell.bg != bg {
+                    write!(out, "{}", color::Bg(cell.bg)).unwrap();
+                    bg = cell.bg;
+                }
+                out.push_str(&cell.symbol);
+
+                next = Some((x + cell.width(), y));
+                self.front.set(x, y, cell.clone());
+            }
+        }
+
+        if out.is_empty() {
+            return;
+        }
+
+        if !attrs.is_empty() {
+            write!(out, "{}", style::Reset).unwrap();
+        } else {
+            if fg != Colour::Reset {
+                write!(out, "{}", color::Fg(color::Reset)).unwrap();
+            }
+            if bg != Colour::Reset {
+                write!(out, "{}", color::Bg(color::Reset)).unwrap();
+            }
+        }
+
+        screen.write_all(out.as_bytes()).unwrap();
+        screen.flush().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use termion::{color, cursor};
+
+    use super::{Buffer, Cell, Compositor, Style};
+    use crate::structs::Colour;
+
+    /// push a frame, returning what was written to screen
+    fn push(compositor: &mut Compositor, frame: &Buffer) -> String {
+        let mut out = Vec::new();
+        compositor.push(frame, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn unchanged_frame_writes_nothing() {
+        let mut compositor = Compositor::default();
+        let mut frame = Buffer::new(4, 2);
+        frame.set(1, 1, Cell::new("a"));
+        push(&mut compositor, &frame);
+
+        assert_eq!(push(&mut compositor, &frame), "");
+    }
+
+    #[test]
+    fn consecutive_cells_share_goto() {
+        let mut compositor = Compositor::default();
+        let mut frame = Buffer::new(4, 2);
+        push(&mut compositor, &frame);
+
+        frame.set(1, 0, Cell::new("a"));
+        frame.set(2, 0, Cell::new("b"));
+        frame.set(0, 1, Cell::new("c"));
+        assert_eq!(
+            push(&mut compositor, &frame),
+            format!("{}ab{}c", cursor::Goto(2, 1), cursor::Goto(1, 2))
+        );
+    }
+
+    #[test]
+    fn colours_change_once_and_reset() {
+        let mut compositor = Compositor::default();
+        let mut frame = Buffer::new(3, 1);
+        push(&mut compositor, &frame);
+
+        let red = Cell::new_styled("a", Colour::Red, Colour::Reset, Style::default());
+        frame.set(0, 0, red.clone());
+        frame.set(1, 0, red);
+        assert_eq!(
+            push(&mut compositor, &frame),
+            format!(
+                "{}{}aa{}",
+                cursor::Goto(1, 1),
+                color::Fg(Colour::Red),
+                color::Fg(color::Reset)
+            )
+        );
+    }
+
+    #[test]
+    fn resize_clears_screen() {
+        let mut compositor = Compositor::default();
+        push(&mut compositor, &Buffer::new(2, 1));
+
+        assert_eq!(
+            push(&mut compositor, &Buffer::new(3, 1)),
+            termion::clear::All.to_string()
+        );
+    }
+
+    #[test]
+    fn wide_cells_skip_continuations() {
+        let mut compositor = Compositor::default();
+        let mut frame = Buffer::new(4, 1);
+        push(&mut compositor, &frame);
+
+        frame.set(0, 0, Cell::new("世"));
+        frame.set(2, 0, Cell::new("a"));
+        assert_eq!(
+            push(&mut compositor, &frame),
+            format!("{}世a", cursor::Goto(1, 1))
+        );
+    }
+}
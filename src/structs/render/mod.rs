@@ -0,0 +1,14 @@
+mod buffer;
+pub use buffer::*;
+
+mod cell;
+pub use cell::*;
+
+mod compositor;
+pub use compositor::*;
+
+mod style;
+pub use style::*;
+
+mod view;
+pub use view::*;
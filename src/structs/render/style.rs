@@ -0,0 +1,124 @@
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use termion::style;
+
+/// a single text attribute
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+pub enum Attribute {
+    #[serde(rename = "bold")]
+    Bold,
+    #[serde(rename = "faint")]
+    Faint,
+    #[serde(rename = "italic")]
+    Italic,
+    #[serde(rename = "underline")]
+    Underline,
+    #[serde(rename = "blink")]
+    Blink,
+    #[serde(rename = "reverse")]
+    Reverse,
+    #[serde(rename = "strikethrough")]
+    Strikethrough,
+}
+
+impl Attribute {
+    /// every attribute, in the order of their bits
+    pub const ALL: [Self; 7] = [
+        Self::Bold,
+        Self::Faint,
+        Self::Italic,
+        Self::Underline,
+        Self::Blink,
+        Self::Reverse,
+        Self::Strikethrough,
+    ];
+
+    /// bit of the attribute in a style
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bold => style::Bold.fmt(f),
+            Self::Faint => style::Faint.fmt(f),
+            Self::Italic => style::Italic.fmt(f),
+            Self::Underline => style::Underline.fmt(f),
+            Self::Blink => style::Blink.fmt(f),
+            Self::Reverse => style::Invert.fmt(f),
+            Self::Strikethrough => style::CrossedOut.fmt(f),
+        }
+    }
+}
+
+/// a set of text attributes
+///
+/// serialised as a list of attributes, e.g. `["bold", "underline"]`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
+#[serde(from = "Vec<Attribute>", into = "Vec<Attribute>")]
+pub struct Style(u8);
+
+impl Style {
+    /// check if the attribute is set
+    pub fn contains(&self, attribute: Attribute) -> bool {
+        self.0 & attribute.bit() != 0
+    }
+
+    /// set an attribute
+    pub fn insert(&mut self, attribute: Attribute) {
+        self.0 |= attribute.bit()
+    }
+
+    /// check if no attributes are set
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// list all attributes that are set
+    pub fn attributes(&self) -> Vec<Attribute> {
+        Attribute::ALL
+            .into_iter()
+            .filter(|attribute| self.contains(*attribute))
+            .collect()
+    }
+}
+
+/// described the same way it is serialised
+impl JsonSchema for Style {
+    fn schema_name() -> String {
+        "Style".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        <Vec<Attribute>>::json_schema(gen)
+    }
+}
+
+impl From<Vec<Attribute>> for Style {
+    fn from(value: Vec<Attribute>) -> Self {
+        let mut style = Self::default();
+        value
+            .into_iter()
+            .for_each(|attribute| style.insert(attribute));
+        style
+    }
+}
+
+impl From<Style> for Vec<Attribute> {
+    fn from(value: Style) -> Self {
+        value.attributes()
+    }
+}
+
+/// writes escape codes turning on all the attributes
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.attributes()
+            .iter()
+            .try_for_each(|attribute| attribute.fmt(f))
+    }
+}
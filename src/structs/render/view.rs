@@ -0,0 +1,84 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::structs::Rect;
+
+use super::{Buffer, Cell};
+
+/// a part of a buffer, with its own coordinates starting from the top left corner of rect
+/// anything outside of rect is clipped
+pub struct View<'a> {
+    buffer: &'a mut Buffer,
+    rect: Rect,
+}
+
+impl<'a> View<'a> {
+    /// construct new self
+    pub fn new(buffer: &'a mut Buffer, rect: Rect) -> Self {
+        Self { buffer, rect }
+    }
+
+    /// returns the rect self is looking at
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// set cell at location relative to self, does nothing if out of bounds
+    pub fn set(&mut self, x: u32, y: u32, mut cell: Cell) {
+        if x >= self.rect.width || y >= self.rect.height {
+            return;
+        }
+
+        // a wide cell should not spill out of the view
+        if cell.width() == 2 && x + 1 >= self.rect.width {
+            cell.symbol = " ".to_string();
+        }
+
+        self.buffer.set(self.rect.x + x, self.rect.y + y, cell)
+    }
+
+    /// write a string starting at location, going right
+    /// returns number of columns taken up
+    pub fn set_string(&mut self, x: u32, y: u32, s: &str, template: &Cell) -> u32 {
+        let mut offset = 0;
+        for grapheme in s.graphemes(true) {
+            let cell = Cell::new_styled(grapheme, template.fg, template.bg, template.style);
+            let width = cell.width();
+            self.set(x.saturating_add(offset), y, cell);
+            offset += width;
+        }
+        offset
+    }
+
+    /// set every cell in a rectangle relative to self, None makes them transparent
+    pub fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, cell: Option<Cell>) {
+        let rect = self.rect.inner(&Rect::new(x, y, width, height));
+        match cell {
+            // wide cells are placed every other column
+            // with a space in the last column if it is left over
+            Some(cell) => {
+                let mut last = cell.clone();
+                last.symbol = " ".to_string();
+                for y in rect.y..rect.bottom() {
+                    for x in (rect.x..rect.right()).step_by(cell.width() as usize) {
+                        let cell = if x + cell.width() > rect.right() {
+                            last.clone()
+                        } else {
+                            cell.clone()
+                        };
+                        self.set(x - self.rect.x, y - self.rect.y, cell)
+                    }
+                }
+            }
+            None => self
+                .buffer
+                .fill(rect.x, rect.y, rect.width, rect.height, None),
+        }
+    }
+
+    /// make every cell in self transparent
+    pub fn clear(&mut self) {
+        let rect = self.rect;
+        self.buffer
+            .fill(rect.x, rect.y, rect.width, rect.height, None)
+    }
+}
@@ -6,3 +6,12 @@ pub use requestcontent::*;
 
 mod render_request;
 pub use render_request::*;
+
+mod property;
+pub use property::*;
+
+mod pool_target;
+pub use pool_target::*;
+
+mod pool_scope;
+pub use pool_scope::*;
@@ -0,0 +1,94 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::structs::Discriminator;
+
+/// who can see a key in a pool, and so who can write to it
+/// the owner of the pool can always see all of its keys
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Default, JsonSchema)]
+pub enum PoolScope {
+    /// only the owner of the pool
+    #[serde(rename = "private")]
+    #[default]
+    Private,
+    /// components directly in the space the pool belongs to
+    #[serde(rename = "space")]
+    Space,
+    /// components anywhere under the space the pool belongs to
+    #[serde(rename = "descendants")]
+    Descendants,
+    /// every component
+    #[serde(rename = "global")]
+    Global,
+}
+
+impl PoolScope {
+    /// every scope, from the tightest
+    pub const ALL: [Self; 4] = [Self::Private, Self::Space, Self::Descendants, Self::Global];
+
+    /// if a component can see a key with this scope
+    /// the space of a pool is the owner itself for spaces, or the space the owner is in
+    pub fn visible(
+        &self,
+        owner: &Discriminator,
+        space: &Discriminator,
+        component: &Discriminator,
+    ) -> bool {
+        component == owner
+            || match self {
+                Self::Private => false,
+                Self::Space => component.clone().immediate_parent().as_ref() == Some(space),
+                Self::Descendants => space.is_parent_of(component),
+                Self::Global => true,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PoolScope;
+    use crate::structs::Discriminator;
+
+    fn discrim(path: &[u32]) -> Discriminator {
+        Discriminator(path.to_vec())
+    }
+
+    /// which of a set of components can see a key in the pool of [1, 2]
+    /// a process in the space [1]
+    fn seen_by(scope: PoolScope) -> Vec<bool> {
+        let (owner, space) = (discrim(&[1, 2]), discrim(&[1]));
+        [&[1, 2][..], &[1], &[1, 3], &[1, 3, 4], &[5], &[]]
+            .iter()
+            .map(|path| scope.visible(&owner, &space, &discrim(path)))
+            .collect()
+    }
+
+    #[test]
+    fn private_only_seen_by_owner() {
+        assert_eq!(
+            seen_by(PoolScope::Private),
+            [true, false, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn space_seen_by_siblings() {
+        assert_eq!(
+            seen_by(PoolScope::Space),
+            [true, false, true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn descendants_seen_under_space() {
+        assert_eq!(
+            seen_by(PoolScope::Descendants),
+            [true, false, true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn global_seen_by_all() {
+        assert_eq!(seen_by(PoolScope::Global), [true; 6]);
+    }
+}
@@ -0,0 +1,34 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::structs::Discriminator;
+
+/// whose pool a pool request is for
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Default, JsonSchema)]
+#[serde(tag = "type")]
+pub enum PoolTarget {
+    /// the pool of the sender
+    #[serde(rename = "self")]
+    #[default]
+    Own,
+    /// the pool of the space the sender is in
+    #[serde(rename = "parent")]
+    Parent,
+    /// the pool of a specific component
+    #[serde(rename = "component")]
+    Component { discrim: Discriminator },
+}
+
+impl PoolTarget {
+    /// the component owning the pool, as seen from the sender
+    pub fn resolve(&self, sender: &Discriminator) -> Option<Discriminator> {
+        match self {
+            Self::Own => Some(sender.clone()),
+            Self::Parent => sender
+                .clone()
+                .immediate_parent()
+                .filter(|parent| !parent.is_empty()),
+            Self::Component { discrim } => Some(discrim.clone()),
+        }
+    }
+}
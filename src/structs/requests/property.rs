@@ -0,0 +1,29 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// properties of a component that can be requested
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(tag = "type")]
+pub enum Property {
+    /// discriminator of the component
+    #[serde(rename = "discrim")]
+    Discrim,
+    /// label of the component
+    #[serde(rename = "label")]
+    Label,
+    /// the space containing the component
+    #[serde(rename = "parent")]
+    Parent,
+    /// other components in the same space
+    #[serde(rename = "siblings")]
+    Siblings,
+    /// the innermost focused space
+    #[serde(rename = "focused space")]
+    FocusedSpace,
+    /// region of the screen the component draws in
+    #[serde(rename = "region")]
+    Region,
+    /// subscriptions of the component with their priorities
+    #[serde(rename = "subscriptions")]
+    Subscriptions,
+}
@@ -1,24 +1,68 @@
-use serde::Deserialize;
-use std::io::Write;
-use termion::{color, cursor};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-use crate::values::SCREEN;
+use crate::structs::{Canvas, Cell, Style, View};
 
-#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
 #[serde(tag = "type")]
 pub enum RenderRequest {
     #[serde(rename = "set char")]
     /// set a character at a specific location
-    SetChar { x: u32, y: u32, c: char },
+    /// c can be any grapheme cluster, wide ones take up 2 columns
+    SetChar { x: u32, y: u32, c: String },
     #[serde(rename = "set colouredchar")]
     /// set a character at a specific locaton with fg and bg colours
+    /// and optionally text attributes
     SetCharColoured {
         x: u32,
         y: u32,
-        c: char,
+        c: String,
         fg: Colour,
         bg: Colour,
+        #[serde(default)]
+        style: Style,
     },
+    #[serde(rename = "set string")]
+    /// write a string starting at a specific location, going right
+    SetString {
+        x: u32,
+        y: u32,
+        s: String,
+        #[serde(default)]
+        fg: Colour,
+        #[serde(default)]
+        bg: Colour,
+        #[serde(default)]
+        style: Style,
+    },
+    #[serde(rename = "fill rect")]
+    /// fill a rectangle with a character
+    FillRect {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        c: String,
+        #[serde(default)]
+        fg: Colour,
+        #[serde(default)]
+        bg: Colour,
+        #[serde(default)]
+        style: Style,
+    },
+    #[serde(rename = "clear rect")]
+    /// remove everything drawn in a rectangle
+    ClearRect {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    #[serde(rename = "clear all")]
+    /// remove everything drawn in the region of the sender,
+    /// its place in the layout or else the whole space it is in,
+    /// not the whole screen
+    ClearAll,
     #[serde(rename = "flush")]
     /// flush all changes
     Flush,
@@ -38,78 +82,85 @@ pub enum RenderRequest {
 }
 
 impl RenderRequest {
-    pub fn draw(&self, mut flush: bool) {
+    /// draw to the region of a space buffer
+    /// coordinates are relative to the region
+    pub fn draw(&self, view: &mut View, canvas: &Canvas) {
         match self {
-            Self::SetChar { x, y, c } => {
-                write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}{c}",
-                    termion::cursor::Goto(*x as u16 + 1, *y as u16 + 1)
-                )
-                .unwrap();
-            }
-            Self::SetCharColoured { x, y, c, fg, bg } => {
-                write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}{}{}{c}{}{}",
-                    color::Fg(*fg),
-                    color::Bg(*bg),
-                    termion::cursor::Goto(*x as u16 + 1, *y as u16 + 1),
-                    color::Fg(termion::color::Reset),
-                    color::Bg(termion::color::Reset),
-                )
-                .unwrap();
-            }
-            Self::Flush => flush = true,
-            Self::SetCursorStyle { style } => match style {
-                CursorStyle::BlinkingBar => write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}",
-                    cursor::BlinkingBar
-                ),
-                CursorStyle::BlinkingBlock => write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}",
-                    cursor::BlinkingBlock
-                ),
-                CursorStyle::BlinkingUnderline => write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}",
-                    cursor::BlinkingUnderline
-                ),
-                CursorStyle::SteadyBar => write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}",
-                    cursor::SteadyBar
-                ),
-                CursorStyle::SteadyBlock => write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}",
-                    cursor::SteadyBlock
-                ),
-                CursorStyle::SteadyUnderline => write!(
-                    unsafe { SCREEN.get_mut() }.unwrap(),
-                    "{}",
-                    cursor::SteadyUnderline
-                ),
+            Self::SetChar { x, y, c } => view.set(*x, *y, Cell::new(c)),
+            Self::SetCharColoured {
+                x,
+                y,
+                c,
+                fg,
+                bg,
+                style,
+            } => view.set(*x, *y, Cell::new_styled(c, *fg, *bg, *style)),
+            Self::SetString {
+                x,
+                y,
+                s,
+                fg,
+                bg,
+                style,
+            } => {
+                view.set_string(*x, *y, s, &Cell::new_styled(" ", *fg, *bg, *style));
             }
-            .unwrap(),
-            Self::HideCursor => {
-                write!(unsafe { SCREEN.get_mut() }.unwrap(), "{}", cursor::Hide).unwrap()
-            }
-            Self::ShowCursor => {
-                write!(unsafe { SCREEN.get_mut() }.unwrap(), "{}", cursor::Show).unwrap()
-            }
-            Self::RenderMultiple { tasks } => tasks.iter().for_each(|item| item.draw(false)),
+            Self::FillRect {
+                x,
+                y,
+                width,
+                height,
+                c,
+                fg,
+                bg,
+                style,
+            } => view.fill(
+                *x,
+                *y,
+                *width,
+                *height,
+                Some(Cell::new_styled(c, *fg, *bg, *style)),
+            ),
+            Self::ClearRect {
+                x,
+                y,
+                width,
+                height,
+            } => view.fill(*x, *y, *width, *height, None),
+            Self::ClearAll => view.clear(),
+            // flushing is done by the compositor
+            Self::Flush => {}
+            // cursor is shared by the whole terminal
+            // so it does not go into the buffer
+            Self::SetCursorStyle { style } => canvas.backend().set_cursor_style(style),
+            Self::HideCursor => canvas.backend().set_cursor_visible(false),
+            Self::ShowCursor => canvas.backend().set_cursor_visible(true),
+            Self::RenderMultiple { tasks } => tasks.iter().for_each(|item| item.draw(view, canvas)),
         }
+    }
 
-        if flush {
-            unsafe { SCREEN.get_mut() }.unwrap().flush().unwrap();
+    /// if the request contains a flush
+    pub fn flushes(&self) -> bool {
+        match self {
+            Self::Flush => true,
+            Self::RenderMultiple { tasks } => tasks.iter().any(Self::flushes),
+            _ => false,
+        }
+    }
+
+    /// if the request uses text attributes
+    pub fn styled(&self) -> bool {
+        match self {
+            Self::SetCharColoured { style, .. }
+            | Self::SetString { style, .. }
+            | Self::FillRect { style, .. } => !style.is_empty(),
+            Self::RenderMultiple { tasks } => tasks.iter().any(Self::styled),
+            _ => false,
         }
     }
 }
 
-#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
 pub enum CursorStyle {
     #[serde(rename = "blinking bar")]
     BlinkingBar,
@@ -125,7 +176,7 @@ pub enum CursorStyle {
     SteadyUnderline,
 }
 
-#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default, JsonSchema)]
 #[serde(tag = "type")]
 pub enum Colour {
     #[serde(rename = "black")]
@@ -163,6 +214,7 @@ pub enum Colour {
     LightYellow,
 
     #[serde(rename = "reset")]
+    #[default]
     Reset,
     #[serde(rename = "ansi")]
     Ansi { value: u8 },
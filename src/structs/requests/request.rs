@@ -1,11 +1,14 @@
-use crate::structs::{Discriminator, Event, Packet, Response, ResponseContent, Subscription};
+use crate::structs::{
+    Canvas, Discriminator, Encoding, Event, Packet, PoolTarget, Property, Response,
+    ResponseContent, ResponseError, Subscription,
+};
+use schemars::JsonSchema;
 
 use super::RequestContent;
-use serde::Deserialize;
-use tokio::sync::OnceCell;
+use serde::{Deserialize, Serialize};
 
 /// a signal that comes from a subprocess
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct Request {
     /// reciever
     target: Discriminator,
@@ -15,24 +18,53 @@ pub struct Request {
     id: u32,
 }
 
-/// for generated requests not coming from a process
-static mut REQ_ALTID: OnceCell<u32> = OnceCell::const_new_with(u32::MAX);
+impl Request {
+    /// decode a request sent by a component
+    /// on failure, returns the error along with the request id if it can be found
+    pub fn decode(frame: &[u8], encoding: Encoding) -> Result<Self, (ResponseError, Option<u32>)> {
+        let reason = match encoding.decode(frame) {
+            Ok(request) => return Ok(request),
+            Err(reason) => reason,
+        };
 
-fn req_id() -> u32 {
-    let id = unsafe { REQ_ALTID.get_mut() }.unwrap();
-    *id -= 1;
-    *id
-}
+        // the id is looked up separately, so the error can still be responded to
+        let id = encoding
+            .decode::<serde_json::Value>(frame)
+            .ok()
+            .and_then(|value| value.get("id").and_then(serde_json::Value::as_u64))
+            .and_then(|id| u32::try_from(id).ok());
 
-impl Request {
-    /// construct new self
-    pub fn new(target: Discriminator, content: RequestContent) -> Self {
+        // serde does not expose the kind of error, only the message
+        let error = match reason
+            .split_once("unknown variant `")
+            .and_then(|(_, rest)| rest.split_once('`'))
+        {
+            Some((variant, _)) => ResponseError::UnknownVariant {
+                variant: variant.to_string(),
+            },
+            None => ResponseError::ParseError { reason },
+        };
+        Err((error, id))
+    }
+
+    /// construct new self, generated by ccanvas
+    pub fn new(canvas: &Canvas, target: Discriminator, content: RequestContent) -> Self {
         Self {
             target,
             content,
-            id: req_id(),
+            id: canvas.request_id(),
         }
     }
+
+    /// construct new self with an id chosen by the component
+    pub fn new_with_id(target: Discriminator, content: RequestContent, id: u32) -> Self {
+        Self {
+            target,
+            content,
+            id,
+        }
+    }
+
     /// returns discrim of target component
     pub fn target(&self) -> &Discriminator {
         &self.target
@@ -54,15 +86,118 @@ impl Request {
     }
 
     /// send self to master space, and wait for response
-    pub async fn send(self) -> Response {
+    pub async fn send(self, canvas: &Canvas) -> Response {
         let (packet, recv) = Packet::new(self);
-        Event::send(Event::from_packet(packet));
+        canvas.send(Event::from_packet(packet));
 
         if let Ok(res) = recv.await {
             res
         } else {
-            Response::new(ResponseContent::Undelivered)
+            Response::new(canvas, ResponseContent::Undelivered)
+        }
+    }
+
+    /// fill in the sender of a request sent by a component
+    /// and point it at the space that should handle it
+    pub fn address(&mut self, sender: &Discriminator) -> Result<(), ResponseError> {
+        // the space the component is in
+        let parent = || sender.clone().immediate_parent().unwrap();
+
+        match &mut self.content {
+            RequestContent::Subscribe {
+                channel, component, ..
+            }
+            | RequestContent::Unsubscribe { channel, component } => {
+                // the subscription is recorded in the space the component is in
+                // and get added as into the passes
+                channel.resolve_pools(sender)?;
+                *component = Some(sender.clone());
+                self.target = parent();
+            }
+            RequestContent::Drop { discrim } => {
+                // this goes to parent space
+                let to_drop = discrim.get_or_insert_with(|| sender.clone()).clone();
+                self.target = to_drop
+                    .immediate_parent()
+                    .ok_or(ResponseError::InvalidTarget)?;
+            }
+            RequestContent::Render { component, .. } => {
+                // this goes to the space the component is in
+                // which draws it in the region of the component
+                *component = Some(sender.clone());
+                self.target = parent();
+            }
+            RequestContent::GetScreenSize { component } => {
+                // this goes to the space the component is in
+                // which knows the region of the component
+                *component = Some(sender.clone());
+                self.target = parent();
+            }
+            RequestContent::Get {
+                property,
+                component,
+            } => {
+                // properties are stored in the parent space of the component
+                // except the focused space, which is found from the master space
+                let component = component.get_or_insert_with(|| sender.clone()).clone();
+                self.target = if property == &Property::FocusedSpace {
+                    Discriminator::master()
+                } else {
+                    component
+                        .clone()
+                        .immediate_parent()
+                        .filter(|parent| !parent.is_empty())
+                        .unwrap_or(component)
+                };
+            }
+            RequestContent::PoolGet {
+                pool, sender: from, ..
+            }
+            | RequestContent::PoolSet {
+                pool, sender: from, ..
+            }
+            | RequestContent::PoolDelete {
+                pool, sender: from, ..
+            }
+            | RequestContent::PoolCompareAndSet {
+                pool, sender: from, ..
+            }
+            | RequestContent::PoolIncrement {
+                pool, sender: from, ..
+            }
+            | RequestContent::PoolDeleteIf {
+                pool, sender: from, ..
+            }
+            | RequestContent::PoolList {
+                pool, sender: from, ..
+            } => {
+                // pools are stored in the parent space of the component, like properties
+                let owner = pool.resolve(sender).ok_or(ResponseError::InvalidTarget)?;
+                self.target = owner
+                    .clone()
+                    .immediate_parent()
+                    .filter(|parent| !parent.is_empty())
+                    .unwrap_or(owner.clone());
+                *pool = PoolTarget::Component { discrim: owner };
+                // so the space can tell which keys the sender can see
+                *from = Some(sender.clone());
+            }
+            RequestContent::Spawn { .. } | RequestContent::SetLayout { .. } => {
+                // this goes to the space the component is in only when target is not specified
+                if self.target.is_empty() {
+                    self.target = parent();
+                }
+            }
+            // mark self as sender
+            RequestContent::Message { sender: from, .. } => *from = sender.clone(),
+            RequestContent::NewSpace { .. } | RequestContent::FocusAt => {}
+            // these are answered by the component itself
+            RequestContent::Hello { .. }
+            | RequestContent::ConfirmRecieve { .. }
+            | RequestContent::SetSocket { .. } => return Err(ResponseError::InvalidRequest),
         }
+
+        Ok(())
     }
 
     /// get request id
@@ -1,15 +1,30 @@
+use schemars::JsonSchema;
+use serde_json::Value;
 use std::path::PathBuf;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::structs::{Discriminator, Subscription};
+use crate::structs::{Discriminator, Encoding, Feature, Layout, Subscription, Transport};
 
-use super::RenderRequest;
+use super::{PoolScope, PoolTarget, Property, RenderRequest};
 
 /// variations of requests
-#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 #[serde(tag = "type")]
 pub enum RequestContent {
+    #[serde(rename = "hello")]
+    /// declare the protocol version and features spoken by the component
+    /// should be the first request sent
+    Hello {
+        version: u32,
+        #[serde(default)]
+        features: Vec<Feature>,
+        /// switch to this encoding after the handshake
+        /// only applies to persistent connections
+        #[serde(default)]
+        encoding: Encoding,
+    },
+
     #[serde(rename = "confirm recieve")]
     /// confirm that an event has been recieved
     ConfirmRecieve {
@@ -43,7 +58,12 @@ pub enum RequestContent {
 
     #[serde(rename = "render")]
     /// render something to the terminal
-    Render { content: RenderRequest, flush: bool },
+    /// coordinates are relative to the region of the component
+    Render {
+        content: RenderRequest,
+        flush: bool,
+        component: Option<Discriminator>,
+    },
 
     #[serde(rename = "spawn")]
     /// spawn a new process
@@ -51,6 +71,8 @@ pub enum RequestContent {
         command: String,
         args: Vec<String>,
         label: String,
+        #[serde(default)]
+        transport: Transport,
     },
 
     #[serde(rename = "message")]
@@ -70,9 +92,173 @@ pub enum RequestContent {
     /// focus a specific space
     #[serde(rename = "focus at")]
     FocusAt,
+
+    /// get the current terminal size, and the region of the component
+    #[serde(rename = "get screen size")]
+    GetScreenSize { component: Option<Discriminator> },
+
+    /// change how children of a space are arranged
+    /// defaults to the parent space if target is not specified
+    #[serde(rename = "set layout")]
+    SetLayout { layout: Layout },
+
+    /// get a property of a component
+    /// defaults to the sender if component is not specified
+    #[serde(rename = "get")]
+    Get {
+        property: Property,
+        component: Option<Discriminator>,
+    },
+
+    /// get the value of a key in a pool
+    /// or the value at a json pointer inside it
+    #[serde(rename = "pool get")]
+    PoolGet {
+        key: String,
+        #[serde(default)]
+        pointer: String,
+        #[serde(default)]
+        pool: PoolTarget,
+        #[serde(default)]
+        sender: Option<Discriminator>,
+    },
+
+    /// set the value of a key in a pool
+    /// or the value at a json pointer inside it, if the key is set
+    #[serde(rename = "pool set")]
+    PoolSet {
+        key: String,
+        #[serde(default)]
+        pointer: String,
+        value: Value,
+        /// who can see and write the key, only the owner of the pool can set it
+        /// keeps the current scope if not specified, new keys get the tightest
+        /// scope that includes the sender
+        #[serde(default)]
+        scope: Option<PoolScope>,
+        #[serde(default)]
+        pool: PoolTarget,
+        #[serde(default)]
+        sender: Option<Discriminator>,
+    },
+
+    /// remove a key from a pool
+    /// or the value at a json pointer inside it
+    #[serde(rename = "pool delete")]
+    PoolDelete {
+        key: String,
+        #[serde(default)]
+        pointer: String,
+        #[serde(default)]
+        pool: PoolTarget,
+        #[serde(default)]
+        sender: Option<Discriminator>,
+    },
+
+    /// set a key only if its current value is expected, null for not set
+    #[serde(rename = "pool compare and set")]
+    PoolCompareAndSet {
+        key: String,
+        expected: Option<Value>,
+        value: Value,
+        /// who can see and write the key, same as in pool set
+        #[serde(default)]
+        scope: Option<PoolScope>,
+        #[serde(default)]
+        pool: PoolTarget,
+        #[serde(default)]
+        sender: Option<Discriminator>,
+    },
+
+    /// add to the integer value of a key, negative to subtract
+    /// a key that is not set counts as 0
+    #[serde(rename = "pool increment")]
+    PoolIncrement {
+        key: String,
+        #[serde(default = "one")]
+        by: i64,
+        /// who can see and write the key, same as in pool set
+        #[serde(default)]
+        scope: Option<PoolScope>,
+        #[serde(default)]
+        pool: PoolTarget,
+        #[serde(default)]
+        sender: Option<Discriminator>,
+    },
+
+    /// remove a key only if its current value is expected
+    #[serde(rename = "pool delete if")]
+    PoolDeleteIf {
+        key: String,
+        expected: Value,
+        #[serde(default)]
+        pool: PoolTarget,
+        #[serde(default)]
+        sender: Option<Discriminator>,
+    },
+
+    /// list keys in a pool starting with prefix
+    #[serde(rename = "pool list")]
+    PoolList {
+        #[serde(default)]
+        prefix: String,
+        #[serde(default)]
+        pool: PoolTarget,
+        #[serde(default)]
+        sender: Option<Discriminator>,
+    },
+}
+
+/// default amount to increment by
+fn one() -> i64 {
+    1
 }
 
 impl RequestContent {
+    /// protocol feature the request depends on, if any
+    pub fn feature(&self) -> Option<Feature> {
+        match self {
+            Self::Spawn {
+                transport: Transport::Stdio,
+                ..
+            } => Some(Feature::Stdio),
+            Self::Render { content, .. } if content.styled() => Some(Feature::Styles),
+            Self::SetLayout { .. } => Some(Feature::Layouts),
+            Self::Get { .. } => Some(Feature::Properties),
+            _ if self.pool().is_some() => Some(Feature::Pool),
+            _ => None,
+        }
+    }
+
+    /// whose pool the request is for, if it is a pool request
+    pub fn pool(&self) -> Option<&PoolTarget> {
+        match self {
+            Self::PoolGet { pool, .. }
+            | Self::PoolSet { pool, .. }
+            | Self::PoolDelete { pool, .. }
+            | Self::PoolCompareAndSet { pool, .. }
+            | Self::PoolIncrement { pool, .. }
+            | Self::PoolDeleteIf { pool, .. }
+            | Self::PoolList { pool, .. } => Some(pool),
+            _ => None,
+        }
+    }
+
+    /// component that sent the request, if it is recorded
+    pub fn sender(&self) -> Option<&Discriminator> {
+        match self {
+            Self::Message { sender, .. } => Some(sender),
+            Self::PoolGet { sender, .. }
+            | Self::PoolSet { sender, .. }
+            | Self::PoolDelete { sender, .. }
+            | Self::PoolCompareAndSet { sender, .. }
+            | Self::PoolIncrement { sender, .. }
+            | Self::PoolDeleteIf { sender, .. }
+            | Self::PoolList { sender, .. } => sender.as_ref(),
+            _ => None,
+        }
+    }
+
     /// handle the request
     pub fn run(&self) -> Result<(), crate::Error> {
         todo!()
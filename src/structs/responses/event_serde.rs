@@ -1,8 +1,10 @@
-use crate::structs::{Discriminator, Event, KeyEvent, MouseEvent};
+use crate::structs::{Discriminator, Event, KeyEvent, MouseEvent, PoolScope};
+use schemars::JsonSchema;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Serialize, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 #[serde(tag = "type")]
 pub enum EventSerde {
     /// keyboard event
@@ -25,6 +27,15 @@ pub enum EventSerde {
     Focused,
     #[serde(rename = "unfocused")]
     Unfocused,
+    /// a key in the pool of a component is set or removed
+    #[serde(rename = "pool change")]
+    PoolChange {
+        pool: Discriminator,
+        key: String,
+        scope: PoolScope,
+        old: Option<Value>,
+        new: Option<Value>,
+    },
 }
 
 impl EventSerde {
@@ -47,6 +58,20 @@ impl EventSerde {
             },
             Event::Focus { .. } => Self::Focused,
             Event::Unfocus => Self::Unfocused,
+            Event::PoolChange {
+                pool,
+                key,
+                scope,
+                old,
+                new,
+                ..
+            } => Self::PoolChange {
+                pool: pool.clone(),
+                key: key.clone(),
+                scope: *scope,
+                old: old.clone(),
+                new: new.clone(),
+            },
             Event::RequestPacket(_) => unreachable!("should not happend"),
         }
     }
@@ -1,18 +1,12 @@
-use serde::Serialize;
-use tokio::sync::OnceCell;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-use super::ResponseContent;
-
-static mut RESPONSE_ID: OnceCell<u32> = OnceCell::const_new_with(0);
+use crate::structs::Canvas;
 
-fn resp_id() -> u32 {
-    let id = unsafe { RESPONSE_ID.get_mut() }.unwrap();
-    *id += 1;
-    *id
-}
+use super::ResponseContent;
 
 /// a return signal back to a subprocess
-#[derive(Serialize, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 pub struct Response {
     /// the content of the response
     content: ResponseContent,
@@ -28,19 +22,19 @@ pub struct Response {
 
 impl Response {
     /// construct new self
-    pub fn new(content: ResponseContent) -> Self {
+    pub fn new(canvas: &Canvas, content: ResponseContent) -> Self {
         Self {
             content,
-            id: resp_id(),
+            id: canvas.response_id(),
             request: None,
         }
     }
 
     /// construct new self as a response to a request
-    pub fn new_with_request(content: ResponseContent, request: u32) -> Self {
+    pub fn new_with_request(canvas: &Canvas, content: ResponseContent, request: u32) -> Self {
         Self {
             content,
-            id: resp_id(),
+            id: canvas.response_id(),
             request: Some(request),
         }
     }
@@ -50,6 +44,11 @@ impl Response {
         self.id
     }
 
+    /// id of the request this is a response to
+    pub fn request(&self) -> Option<u32> {
+        self.request
+    }
+
     /// get content of self
     pub fn content(&self) -> &ResponseContent {
         &self.content
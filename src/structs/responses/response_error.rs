@@ -1,6 +1,11 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use std::fmt;
 
-#[derive(Serialize, Clone, PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+use crate::structs::Feature;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 #[serde(tag = "type")]
 pub enum ResponseError {
     /// target component is not found
@@ -9,4 +14,44 @@ pub enum ResponseError {
     /// spawning process failed
     #[serde(rename = "spawn failed")]
     SpawnFailed,
+    /// the component speaks a protocol version ccanvas does not understand
+    #[serde(rename = "incompatible version")]
+    IncompatibleVersion { version: u32, min: u32, max: u32 },
+    /// the request could not be read or is not a valid request
+    #[serde(rename = "parse error")]
+    ParseError { reason: String },
+    /// the request has a type ccanvas does not know about
+    #[serde(rename = "unknown variant")]
+    UnknownVariant { variant: String },
+    /// the request cannot reach its target from where it is sent to
+    #[serde(rename = "invalid target")]
+    InvalidTarget,
+    /// the request is well formed, but makes no sense to its target
+    #[serde(rename = "invalid request")]
+    InvalidRequest,
+    /// the component is not allowed to make the request
+    #[serde(rename = "permission denied")]
+    PermissionDenied,
+    /// the request depends on a feature that is turned off for this canvas
+    #[serde(rename = "feature disabled")]
+    FeatureDisabled { feature: Feature },
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ComponentNotFound => f.write_str("component not found"),
+            Self::SpawnFailed => f.write_str("failed to spawn process"),
+            Self::IncompatibleVersion { version, min, max } => write!(
+                f,
+                "protocol version {version} is not supported, expected {min} to {max}"
+            ),
+            Self::ParseError { reason } => write!(f, "bad request: {reason}"),
+            Self::UnknownVariant { variant } => write!(f, "unknown request type `{variant}`"),
+            Self::InvalidTarget => f.write_str("target is not reachable"),
+            Self::InvalidRequest => f.write_str("request not supported by target"),
+            Self::PermissionDenied => f.write_str("permission denied"),
+            Self::FeatureDisabled { feature } => write!(f, "feature {feature} is disabled"),
+        }
+    }
 }
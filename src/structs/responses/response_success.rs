@@ -1,10 +1,28 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::structs::Discriminator;
+use crate::structs::{Discriminator, Encoding, Feature, Limits, PoolScope, Rect, Subscription};
 
-#[derive(Serialize, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 #[serde(tag = "type")]
 pub enum ResponseSuccess {
+    /// handshake accepted
+    #[serde(rename = "hello")]
+    Hello {
+        /// protocol version spoken by ccanvas
+        version: u32,
+        /// version of ccanvas itself
+        ccanvas: String,
+        /// features supported by both sides
+        features: Vec<Feature>,
+        /// cargo features ccanvas is built with
+        cargo_features: Vec<String>,
+        limits: Limits,
+        /// encoding used from now on
+        encoding: Encoding,
+    },
+
     /// subscription added
     #[serde(rename = "subscribe added")]
     SubscribeAdded,
@@ -40,4 +58,82 @@ pub enum ResponseSuccess {
     /// focus changed successfully
     #[serde(rename = "focus changed")]
     FocusChanged,
+
+    /// current terminal size, and the region of the component that asked
+    #[serde(rename = "screen size")]
+    ScreenSize {
+        width: u32,
+        height: u32,
+        region: Rect,
+    },
+
+    /// discriminator of a component
+    #[serde(rename = "discrim")]
+    Discrim { discrim: Discriminator },
+
+    /// label of a component
+    #[serde(rename = "label")]
+    Label { label: String },
+
+    /// parent space of a component, None for the master space
+    #[serde(rename = "parent")]
+    Parent { discrim: Option<Discriminator> },
+
+    /// other components in the same space
+    #[serde(rename = "siblings")]
+    Siblings { siblings: Vec<Discriminator> },
+
+    /// the innermost focused space
+    #[serde(rename = "focused space")]
+    FocusedSpace { discrim: Discriminator },
+
+    /// region of the screen a component draws in
+    #[serde(rename = "region")]
+    Region { rect: Rect },
+
+    /// layout of a space changed
+    #[serde(rename = "layout set")]
+    LayoutSet,
+
+    /// subscriptions of a component with their priorities
+    #[serde(rename = "subscriptions")]
+    Subscriptions {
+        subscriptions: Vec<(Subscription, Option<u32>)>,
+    },
+
+    /// value of a key in a pool and who can see it, None if it is not set
+    #[serde(rename = "pool value")]
+    PoolValue {
+        key: String,
+        value: Option<Value>,
+        scope: Option<PoolScope>,
+    },
+
+    /// key set in a pool, with the value it replaced
+    #[serde(rename = "pool set")]
+    PoolSet {
+        key: String,
+        previous: Option<Value>,
+    },
+
+    /// key removed from a pool, with the value it had
+    #[serde(rename = "pool deleted")]
+    PoolDeleted { key: String, value: Option<Value> },
+
+    /// result of a conditional change to a pool,
+    /// with the value the key ends up with
+    #[serde(rename = "pool compared")]
+    PoolCompared {
+        key: String,
+        success: bool,
+        value: Option<Value>,
+    },
+
+    /// new value of a key after incrementing
+    #[serde(rename = "pool counter")]
+    PoolCounter { key: String, value: i64 },
+
+    /// keys in a pool
+    #[serde(rename = "pool keys")]
+    PoolKeys { keys: Vec<String> },
 }
@@ -1,10 +1,11 @@
+use schemars::JsonSchema;
 use std::path::PathBuf;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use super::{EventSerde, ResponseError, ResponseSuccess};
 
-#[derive(Serialize, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 #[serde(tag = "type")]
 pub enum ResponseContent {
     #[serde(rename = "undelivered")]
@@ -14,12 +15,33 @@ pub enum ResponseContent {
     Event { content: EventSerde },
 
     #[serde(rename = "error")]
-    Error { content: ResponseError },
+    Error {
+        content: ResponseError,
+        /// human readable description of the error
+        message: String,
+    },
 
     #[serde(rename = "success")]
     Success { content: ResponseSuccess },
 
     /// will not recieve this
     #[serde(rename = "set socket")]
+    #[schemars(skip)]
     SetSocket(PathBuf),
 }
+
+impl From<ResponseSuccess> for ResponseContent {
+    fn from(content: ResponseSuccess) -> Self {
+        Self::Success { content }
+    }
+}
+
+impl ResponseContent {
+    /// an error response with the default message of the error
+    pub fn error(content: ResponseError) -> Self {
+        Self::Error {
+            message: content.to_string(),
+            content,
+        }
+    }
+}
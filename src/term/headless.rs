@@ -0,0 +1,442 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    structs::{Buffer, Cell, CursorStyle, Event},
+    traits::Backend,
+};
+
+/// an in-memory screen, for running without a terminal
+///
+/// clones share the same screen, so one can be kept around
+/// to look at what has been drawn
+/// input events can be sent with `Canvas::send`
+#[derive(Clone)]
+pub struct HeadlessBackend {
+    screen: Arc<Mutex<HeadlessScreen>>,
+}
+
+struct HeadlessScreen {
+    /// last frame drawn
+    grid: Buffer,
+    cursor_visible: bool,
+    cursor_style: Option<CursorStyle>,
+    /// number of frames drawn
+    frames: usize,
+}
+
+impl HeadlessBackend {
+    /// create a blank screen of a size
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            screen: Arc::new(Mutex::new(HeadlessScreen {
+                grid: Buffer::new(width, height),
+                cursor_visible: true,
+                cursor_style: None,
+                frames: 0,
+            })),
+        }
+    }
+
+    /// change the size of the screen
+    /// a `ScreenResize` event should be sent after, just like a real terminal would
+    pub fn resize(&self, width: u32, height: u32) {
+        self.screen.lock().unwrap().grid.resize(width, height);
+    }
+
+    /// a cell on the screen, None if nothing is drawn there
+    pub fn cell(&self, x: u32, y: u32) -> Option<Cell> {
+        self.screen.lock().unwrap().grid.get(x, y).cloned()
+    }
+
+    /// text on a line of the screen, empty cells are spaces
+    pub fn line(&self, y: u32) -> String {
+        let screen = self.screen.lock().unwrap();
+        let (width, _) = screen.grid.size();
+        (0..width)
+            .filter_map(|x| match screen.grid.get(x, y) {
+                Some(cell) if cell.is_continuation() => None,
+                Some(cell) => Some(cell.symbol.clone()),
+                None => Some(" ".to_string()),
+            })
+            .collect()
+    }
+
+    /// text on the whole screen, one line per row
+    pub fn contents(&self) -> String {
+        let (_, height) = self.size();
+        (0..height)
+            .map(|y| self.line(y))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// if the cursor is shown
+    pub fn cursor_visible(&self) -> bool {
+        self.screen.lock().unwrap().cursor_visible
+    }
+
+    /// last cursor style set, None if never changed
+    pub fn cursor_style(&self) -> Option<CursorStyle> {
+        self.screen.lock().unwrap().cursor_style.clone()
+    }
+
+    /// number of frames drawn so far
+    pub fn frames(&self) -> usize {
+        self.screen.lock().unwrap().frames
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn size(&self) -> (u32, u32) {
+        self.screen.lock().unwrap().grid.size()
+    }
+
+    fn draw(&mut self, frame: &Buffer) {
+        let mut screen = self.screen.lock().unwrap();
+        screen.grid = frame.clone();
+        screen.frames += 1;
+    }
+
+    fn set_cursor_style(&mut self, style: &CursorStyle) {
+        self.screen.lock().unwrap().cursor_style = Some(style.clone());
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) {
+        self.screen.lock().unwrap().cursor_visible = visible;
+    }
+
+    // there is no input other than synthetic events
+    fn listen(&mut self, _events: UnboundedSender<Event>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use async_trait::async_trait;
+    use tokio::sync::mpsc::{self, UnboundedSender};
+
+    use super::HeadlessBackend;
+    use crate::{
+        structs::{
+            Buffer, Canvas, CanvasHandle, Cell, Colour, Constraint, CursorStyle, Discriminator,
+            Event, FloatingItem, Layout, LayoutItem, NativeHandle, Rect, RenderRequest,
+            RequestContent, Response, ResponseContent, ResponseSuccess, Style,
+        },
+        traits::{Backend, NativeComponent},
+    };
+
+    /// draws once when added, and ignores events
+    struct Painter(RenderRequest);
+
+    #[async_trait]
+    impl NativeComponent for Painter {
+        async fn start(&self, handle: NativeHandle) {
+            handle.render(self.0.clone(), true).await;
+        }
+
+        async fn event(&self, _event: &Event, _handle: &NativeHandle) -> bool {
+            true
+        }
+    }
+
+    /// asks for the screen size when added, and sends back the response
+    struct Measure(UnboundedSender<Response>);
+
+    #[async_trait]
+    impl NativeComponent for Measure {
+        async fn start(&self, handle: NativeHandle) {
+            let content = RequestContent::GetScreenSize { component: None };
+            let _ = self
+                .0
+                .send(handle.request(Discriminator::default(), content).await);
+        }
+
+        async fn event(&self, _event: &Event, _handle: &NativeHandle) -> bool {
+            true
+        }
+    }
+
+    fn string(x: u32, y: u32, s: &str) -> RenderRequest {
+        RenderRequest::SetString {
+            x,
+            y,
+            s: s.to_string(),
+            fg: Colour::default(),
+            bg: Colour::default(),
+            style: Style::default(),
+        }
+    }
+
+    fn fill(width: u32, height: u32, c: &str) -> RenderRequest {
+        RenderRequest::FillRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+            c: c.to_string(),
+            fg: Colour::default(),
+            bg: Colour::default(),
+            style: Style::default(),
+        }
+    }
+
+    async fn canvas(screen: &HeadlessBackend) -> CanvasHandle {
+        Canvas::builder()
+            .backend(screen.clone())
+            .build()
+            .await
+            .unwrap()
+    }
+
+    async fn new_space(handle: &CanvasHandle, label: &str) -> Discriminator {
+        let response = handle
+            .request(
+                Discriminator::master(),
+                RequestContent::NewSpace {
+                    label: label.to_string(),
+                },
+            )
+            .await;
+        match response.content() {
+            ResponseContent::Success {
+                content: ResponseSuccess::SpaceCreated { discrim },
+            } => discrim.clone(),
+            content => panic!("space not created: {content:?}"),
+        }
+    }
+
+    /// frames are drawn in the background, so wait for one to match
+    fn wait_for(screen: &HeadlessBackend, done: impl Fn(&HeadlessBackend) -> bool) {
+        tokio::task::block_in_place(|| {
+            for _ in 0..200 {
+                if done(screen) {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+        })
+    }
+
+    #[test]
+    fn draws_frames_to_screen() {
+        let screen = HeadlessBackend::new(8, 3);
+        let mut frame = Buffer::new(8, 3);
+        frame
+            .view(Rect::new(0, 0, 8, 3))
+            .set_string(1, 1, "hi 世", &Cell::default());
+        screen.clone().draw(&frame);
+
+        assert_eq!(screen.frames(), 1);
+        assert_eq!(screen.line(1), " hi 世  ");
+        assert_eq!(screen.cell(4, 1).unwrap().symbol, "世");
+        assert!(screen.cell(5, 1).unwrap().is_continuation());
+        assert!(screen.cell(0, 0).is_none());
+        assert_eq!(screen.contents(), "        \n hi 世  \n        ");
+    }
+
+    #[test]
+    fn resize_keeps_content() {
+        let screen = HeadlessBackend::new(4, 1);
+        let mut frame = Buffer::new(4, 1);
+        frame
+            .view(Rect::new(0, 0, 4, 1))
+            .set_string(0, 0, "abcd", &Cell::default());
+        screen.clone().draw(&frame);
+
+        screen.resize(2, 2);
+        assert_eq!(screen.size(), (2, 2));
+        assert_eq!(screen.contents(), "ab\n  ");
+    }
+
+    #[test]
+    fn tracks_cursor() {
+        let screen = HeadlessBackend::new(1, 1);
+        assert!(screen.cursor_visible());
+        assert_eq!(screen.cursor_style(), None);
+
+        let mut backend = screen.clone();
+        backend.set_cursor_visible(false);
+        backend.set_cursor_style(&CursorStyle::SteadyBar);
+        assert!(!screen.cursor_visible());
+        assert_eq!(screen.cursor_style(), Some(CursorStyle::SteadyBar));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn native_component_renders_to_screen() {
+        let screen = HeadlessBackend::new(8, 3);
+        let handle = canvas(&screen).await;
+        handle
+            .add(
+                &Discriminator::master(),
+                "painter",
+                Painter(string(1, 1, "hi 世")),
+            )
+            .await
+            .unwrap();
+
+        wait_for(&screen, |screen| screen.cell(1, 1).is_some());
+        assert_eq!(screen.contents(), "        \n hi 世  \n        ");
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn splits_spaces_in_layout() {
+        let screen = HeadlessBackend::new(10, 2);
+        let handle = canvas(&screen).await;
+        let left = new_space(&handle, "left").await;
+        let right = new_space(&handle, "right").await;
+        let item = |discrim: &Discriminator, size| LayoutItem {
+            discrim: discrim.clone(),
+            size,
+        };
+        handle
+            .request(
+                Discriminator::master(),
+                RequestContent::SetLayout {
+                    layout: Layout::Horizontal {
+                        children: vec![
+                            item(&left, Constraint::Fixed { size: 3 }),
+                            item(&right, Constraint::Ratio { ratio: 1 }),
+                        ],
+                    },
+                },
+            )
+            .await;
+
+        // drawing is clipped to the region of the space
+        handle
+            .add(&left, "left", Painter(fill(10, 1, "a")))
+            .await
+            .unwrap();
+        handle
+            .add(&right, "right", Painter(fill(10, 2, "b")))
+            .await
+            .unwrap();
+
+        wait_for(&screen, |screen| screen.line(1).ends_with('b'));
+        assert_eq!(screen.contents(), "aaabbbbbbb\n   bbbbbbb");
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn moved_space_is_cleared() {
+        let screen = HeadlessBackend::new(6, 1);
+        let handle = canvas(&screen).await;
+        let space = new_space(&handle, "floating").await;
+        let layout = |x| RequestContent::SetLayout {
+            layout: Layout::Floating {
+                children: vec![FloatingItem {
+                    discrim: space.clone(),
+                    rect: Rect::new(x, 0, 4, 1),
+                }],
+            },
+        };
+        handle.request(Discriminator::master(), layout(0)).await;
+        handle
+            .add(&space, "painter", Painter(string(0, 0, "hi")))
+            .await
+            .unwrap();
+        wait_for(&screen, |screen| screen.line(0) == "hi    ");
+        assert_eq!(screen.line(0), "hi    ");
+
+        // what was drawn at the old position should not show up in the new one
+        let frames = screen.frames();
+        handle.request(Discriminator::master(), layout(1)).await;
+        wait_for(&screen, |screen| screen.frames() > frames);
+        assert_eq!(screen.line(0), "      ");
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fills_wide_cells_within_rect() {
+        let screen = HeadlessBackend::new(6, 1);
+        let handle = canvas(&screen).await;
+        handle
+            .add(
+                &Discriminator::master(),
+                "painter",
+                Painter(fill(3, 1, "世")),
+            )
+            .await
+            .unwrap();
+
+        wait_for(&screen, |screen| screen.cell(0, 0).is_some());
+        assert_eq!(screen.line(0), "世    ");
+        assert_eq!(screen.cell(2, 0).unwrap().symbol, " ");
+        assert!(screen.cell(3, 0).is_none());
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unprintable_clusters_become_spaces() {
+        let screen = HeadlessBackend::new(8, 1);
+        let handle = canvas(&screen).await;
+        // a lone combining mark, an escape sequence and a nul
+        handle
+            .add(
+                &Discriminator::master(),
+                "painter",
+                Painter(string(0, 0, "\u{301}a\x1b[2Jb\0")),
+            )
+            .await
+            .unwrap();
+
+        wait_for(&screen, |screen| screen.cell(0, 0).is_some());
+        assert_eq!(screen.line(0), " a [2Jb ");
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn screen_size_has_region_of_sender() {
+        let screen = HeadlessBackend::new(10, 2);
+        let handle = canvas(&screen).await;
+        let left = new_space(&handle, "left").await;
+        let right = new_space(&handle, "right").await;
+        handle
+            .request(
+                Discriminator::master(),
+                RequestContent::SetLayout {
+                    layout: Layout::Horizontal {
+                        children: vec![
+                            LayoutItem {
+                                discrim: left,
+                                size: Constraint::Fixed { size: 3 },
+                            },
+                            LayoutItem {
+                                discrim: right.clone(),
+                                size: Constraint::Ratio { ratio: 1 },
+                            },
+                        ],
+                    },
+                },
+            )
+            .await;
+
+        let (sender, mut responses) = mpsc::unbounded_channel();
+        handle
+            .add(&right, "measure", Measure(sender))
+            .await
+            .unwrap();
+        match responses.recv().await.unwrap().content() {
+            ResponseContent::Success {
+                content:
+                    ResponseSuccess::ScreenSize {
+                        width,
+                        height,
+                        region,
+                    },
+            } => assert_eq!((*width, *height, *region), (10, 2, Rect::new(3, 0, 7, 2))),
+            content => panic!("no screen size: {content:?}"),
+        }
+
+        handle.shutdown().await;
+    }
+}
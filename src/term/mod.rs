@@ -1,5 +1,5 @@
-mod enter;
-pub use enter::*;
+mod terminal;
+pub use terminal::*;
 
-mod exit;
-pub use exit::*;
+mod headless;
+pub use headless::*;
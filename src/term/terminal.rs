@@ -0,0 +1,166 @@
+use std::io::{self, stdout, Read, Stdout, Write};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use termion::{
+    cursor,
+    input::{MouseTerminal, TermRead},
+    raw::{IntoRawMode, RawTerminal},
+    screen::{AlternateScreen, IntoAlternateScreen},
+};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::mpsc::UnboundedSender,
+    task::JoinHandle,
+};
+
+use crate::{
+    structs::{Buffer, Compositor, CursorStyle, Event},
+    traits::Backend,
+};
+
+/// the real terminal, in raw mode and on the alternate screen
+pub struct TerminalBackend {
+    screen: MouseTerminal<AlternateScreen<RawTerminal<Stdout>>>,
+    /// diffs frames against what is on screen
+    compositor: Compositor,
+    /// set on exit, so input stops being read
+    stopped: Arc<AtomicBool>,
+    /// task sending resize events
+    resize: Option<JoinHandle<()>>,
+}
+
+impl TerminalBackend {
+    /// take over the terminal
+    pub fn new() -> io::Result<Self> {
+        let mut screen = MouseTerminal::from(stdout().into_raw_mode()?.into_alternate_screen()?);
+        write!(screen, "{}", termion::clear::All)?;
+        screen.flush()?;
+
+        Ok(Self {
+            screen,
+            compositor: Compositor::default(),
+            stopped: Arc::new(AtomicBool::new(false)),
+            resize: None,
+        })
+    }
+}
+
+/// stdin, which stops being read once the backend exits
+struct Input {
+    stopped: Arc<AtomicBool>,
+}
+
+impl Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut fd = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        // wake up every now and then to check if the backend has exited
+        while !self.stopped.load(Ordering::Relaxed) {
+            if unsafe { libc::poll(&mut fd, 1, 100) } <= 0 {
+                continue;
+            }
+
+            // read from the file descriptor directly, as std::io::stdin
+            // buffers more than asked for, which poll would not know about
+            let read = unsafe { libc::read(fd.fd, buf.as_mut_ptr().cast(), buf.len()) };
+            return match read {
+                -1 => Err(io::Error::last_os_error()),
+                read => Ok(read as usize),
+            };
+        }
+
+        Ok(0)
+    }
+}
+
+impl Backend for TerminalBackend {
+    fn size(&self) -> (u32, u32) {
+        let (width, height) = termion::terminal_size().unwrap_or((80, 24));
+        (width as u32, height as u32)
+    }
+
+    fn draw(&mut self, frame: &Buffer) {
+        self.compositor.push(frame, &mut self.screen);
+    }
+
+    fn set_cursor_style(&mut self, style: &CursorStyle) {
+        match style {
+            CursorStyle::BlinkingBar => write!(self.screen, "{}", cursor::BlinkingBar),
+            CursorStyle::BlinkingBlock => write!(self.screen, "{}", cursor::BlinkingBlock),
+            CursorStyle::BlinkingUnderline => write!(self.screen, "{}", cursor::BlinkingUnderline),
+            CursorStyle::SteadyBar => write!(self.screen, "{}", cursor::SteadyBar),
+            CursorStyle::SteadyBlock => write!(self.screen, "{}", cursor::SteadyBlock),
+            CursorStyle::SteadyUnderline => write!(self.screen, "{}", cursor::SteadyUnderline),
+        }
+        .unwrap();
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) {
+        if visible {
+            write!(self.screen, "{}", cursor::Show).unwrap()
+        } else {
+            write!(self.screen, "{}", cursor::Hide).unwrap()
+        }
+    }
+
+    fn listen(&mut self, events: UnboundedSender<Event>) {
+        let input = events.clone();
+        let stopped = self.stopped.clone();
+        tokio::task::spawn_blocking(move || {
+            Input { stopped }
+                .events()
+                // filter out events that cannot be converted into event
+                .filter_map(|event| -> Option<Event> {
+                    if let Ok(event) = event {
+                        if let Ok(event) = event.try_into() {
+                            return Some(event);
+                        }
+                    }
+                    None
+                })
+                .for_each(|event| {
+                    // send events to master space
+                    let _ = input.send(event);
+                })
+        });
+
+        // SIGWINCH is the only way to listen for window resize event
+        // without pulling in huge dependencies
+        let mut resize = signal(SignalKind::window_change()).unwrap();
+        self.resize = Some(tokio::spawn(async move {
+            while resize.recv().await.is_some() {
+                // send a screen resize event when it is resized
+                let (x, y) = termion::terminal_size().unwrap();
+                if events
+                    .send(Event::ScreenResize(x as u32, y as u32))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }));
+    }
+
+    fn exit(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        if let Some(resize) = self.resize.take() {
+            resize.abort();
+        }
+
+        write!(
+            self.screen,
+            "{}{}{}",
+            cursor::Show,
+            cursor::Restore,
+            termion::screen::ToMainScreen,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,24 @@
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::structs::{Buffer, CursorStyle, Event};
+
+/// where frames are drawn to and input comes from
+pub trait Backend: Send {
+    /// size of the screen as (width, height)
+    fn size(&self) -> (u32, u32);
+
+    /// show a composited frame of the whole screen
+    fn draw(&mut self, frame: &Buffer);
+
+    /// change cursor style
+    fn set_cursor_style(&mut self, style: &CursorStyle);
+
+    /// show or hide the cursor
+    fn set_cursor_visible(&mut self, visible: bool);
+
+    /// start sending input events to the event stream
+    fn listen(&mut self, events: UnboundedSender<Event>);
+
+    /// stop sending input events and restore everything before exiting
+    fn exit(&mut self) {}
+}
@@ -4,7 +4,7 @@ use crate::structs::{Discriminator, Event, Pool, Storage, Unevaluated};
 
 #[async_trait]
 /// a unit of "something"
-pub trait Component {
+pub trait Component: Send + Sync {
     /// unique identifier of what it is
     fn label(&self) -> &str;
 
@@ -20,4 +20,8 @@ pub trait Component {
     /// pass an event into a component
     /// returns true to pass event to next component, false otherwise
     async fn pass(&self, event: &mut Event) -> Unevaluated<bool>;
+
+    /// stop everything the component is running
+    /// called on shutdown, even if something still holds on to the component
+    async fn exit(&self) {}
 }
@@ -1,2 +1,8 @@
 mod component;
 pub use component::*;
+
+mod backend;
+pub use backend::*;
+
+mod native;
+pub use native::*;
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+
+use crate::structs::{Event, NativeHandle};
+
+#[async_trait]
+/// a component written in rust, running inside ccanvas
+///
+/// it lives in a space like a process, but events and requests
+/// are passed directly instead of through sockets
+pub trait NativeComponent: Send + Sync {
+    /// called once the component is added to a space
+    /// the handle can be kept to send requests later
+    async fn start(&self, _handle: NativeHandle) {}
+
+    /// recieve an event the component subscribed to
+    /// returns true to pass event to next component, false otherwise
+    async fn event(&self, event: &Event, handle: &NativeHandle) -> bool;
+}